@@ -0,0 +1,258 @@
+use std::sync::{LazyLock, Mutex};
+
+use chrono::{DateTime, Local};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc, watch};
+
+use crate::{
+    components::timer::{
+        handle_timer_command, read_break_enforced, MILLIS_REMAINING, TIMER_RUNNING,
+    },
+    history::{SessionKind, TaskLabel},
+    interruptions::InterruptionKind,
+    reminders::handle_reminder_command,
+    settings::{handle_settings_command, EmergencySkip, Phase, SETTINGS},
+    state::{PHASE_INDEX, TIMER_EXPIRED},
+    tray::handle_window_command,
+};
+
+#[derive(Debug)]
+pub enum TimerCommand {
+    Start,
+    /// Go back to the stopped timer of the next session
+    Dismiss,
+    /// Start or pause, starting the next session if the timer has expired
+    Toggle,
+    Skip,
+    /// Sent by the break overlay to end a strict break early
+    EmergencySkip,
+    /// Bring the expiry back in a few minutes
+    Snooze,
+    Undo,
+    Redo,
+    /// Log an interruption of the running focus session, `None` asks for its kind and a note
+    Interrupt(Option<InterruptionKind>),
+}
+
+#[derive(Debug)]
+pub enum WindowCommand {
+    Show,
+    ShowSettings,
+}
+
+#[derive(Debug)]
+pub enum SettingsCommand {
+    /// Switch to the preset with this name
+    SwitchPreset(String),
+}
+
+#[derive(Debug)]
+pub enum ReminderCommand {
+    /// Stop the reminder with this id from ringing
+    Dismiss(u64),
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Timer(TimerCommand),
+    Window(WindowCommand),
+    Settings(SettingsCommand),
+    Reminder(ReminderCommand),
+}
+
+impl From<TimerCommand> for Command {
+    fn from(command: TimerCommand) -> Self {
+        Command::Timer(command)
+    }
+}
+
+impl From<WindowCommand> for Command {
+    fn from(command: WindowCommand) -> Self {
+        Command::Window(command)
+    }
+}
+
+impl From<SettingsCommand> for Command {
+    fn from(command: SettingsCommand) -> Self {
+        Command::Settings(command)
+    }
+}
+
+impl From<ReminderCommand> for Command {
+    fn from(command: ReminderCommand) -> Self {
+        Command::Reminder(command)
+    }
+}
+
+/// Session transitions, e.g. for event hooks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEventKind {
+    FocusStart,
+    FocusEnd,
+    BreakStart,
+    BreakEnd,
+    Pause,
+    Resume,
+    Skip,
+}
+
+impl SessionEventKind {
+    pub const ALL: [SessionEventKind; 7] = [
+        SessionEventKind::FocusStart,
+        SessionEventKind::FocusEnd,
+        SessionEventKind::BreakStart,
+        SessionEventKind::BreakEnd,
+        SessionEventKind::Pause,
+        SessionEventKind::Resume,
+        SessionEventKind::Skip,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionEventKind::FocusStart => "focus_start",
+            SessionEventKind::FocusEnd => "focus_end",
+            SessionEventKind::BreakStart => "break_start",
+            SessionEventKind::BreakEnd => "break_end",
+            SessionEventKind::Pause => "pause",
+            SessionEventKind::Resume => "resume",
+            SessionEventKind::Skip => "skip",
+        }
+    }
+
+    pub fn started(is_focus_mode: bool) -> Self {
+        if is_focus_mode {
+            SessionEventKind::FocusStart
+        } else {
+            SessionEventKind::BreakStart
+        }
+    }
+
+    pub fn ended(is_focus_mode: bool) -> Self {
+        if is_focus_mode {
+            SessionEventKind::FocusEnd
+        } else {
+            SessionEventKind::BreakEnd
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SessionEvent {
+    pub kind: SessionEventKind,
+    pub at: DateTime<Local>,
+    /// Kind of the phase the event happened in
+    pub phase: SessionKind,
+    pub phase_name: String,
+    pub planned_secs: u32,
+    pub remaining_secs: u32,
+    pub task: Option<TaskLabel>,
+    /// Number of the focus session in the current cycle, breaks share the number of the focus session before them
+    pub session_number: u32,
+}
+
+static SESSION_EVENTS: LazyLock<broadcast::Sender<SessionEvent>> =
+    LazyLock::new(|| broadcast::channel(64).0);
+
+pub fn emit_event(event: SessionEvent) {
+    // fails only when nobody is listening
+    let _ = SESSION_EVENTS.send(event);
+}
+
+pub fn subscribe_events() -> broadcast::Receiver<SessionEvent> {
+    SESSION_EVENTS.subscribe()
+}
+
+struct CommandChannel {
+    sender: mpsc::UnboundedSender<Command>,
+    // taken by the main window's command handler
+    receiver: Mutex<Option<mpsc::UnboundedReceiver<Command>>>,
+}
+
+static COMMANDS: LazyLock<CommandChannel> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    CommandChannel {
+        sender,
+        receiver: Mutex::new(Some(receiver)),
+    }
+});
+
+/// Can be called from any window or thread, e.g. tray and hotkey callbacks
+pub fn send_command(command: impl Into<Command>) {
+    let _ = COMMANDS.sender.send(command.into());
+}
+
+/// Applies commands as soon as they arrive, used once in the main window
+pub fn use_command_handler() {
+    use_future(|| async {
+        let Some(mut receiver) = COMMANDS.receiver.lock().unwrap().take() else {
+            return;
+        };
+
+        while let Some(command) = receiver.recv().await {
+            match command {
+                Command::Timer(command) => handle_timer_command(command),
+                Command::Window(command) => handle_window_command(command),
+                Command::Settings(command) => handle_settings_command(command),
+                Command::Reminder(command) => handle_reminder_command(command),
+            }
+        }
+    });
+}
+
+/// The main window's timer state as seen by every window
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TimerState {
+    pub phase: Phase,
+    pub millis_remaining: u32,
+    pub timer_running: bool,
+    pub timer_expired: bool,
+    pub break_enforced: bool,
+    /// How the break overlays let the user out of a strict break
+    pub emergency_skip: EmergencySkip,
+    pub emergency_skip_phrase: String,
+}
+
+// GlobalSignals belong to a single VirtualDom, so other windows subscribe to this instead
+static TIMER_STATE: LazyLock<watch::Sender<TimerState>> =
+    LazyLock::new(|| watch::Sender::new(TimerState::default()));
+
+/// Publishes the timer state whenever it changes, used once in the main window
+pub fn use_timer_state_publisher() {
+    use_effect(|| {
+        let settings = SETTINGS.read();
+        let state = TimerState {
+            phase: settings.phase(*PHASE_INDEX.read()),
+            millis_remaining: *MILLIS_REMAINING.read(),
+            timer_running: *TIMER_RUNNING.read(),
+            timer_expired: *TIMER_EXPIRED.read(),
+            break_enforced: read_break_enforced(),
+            emergency_skip: settings.emergency_skip,
+            emergency_skip_phrase: settings.emergency_skip_phrase.clone(),
+        };
+
+        TIMER_STATE.send_if_modified(|current| {
+            if *current == state {
+                return false;
+            }
+            *current = state;
+            true
+        });
+    });
+}
+
+/// Live timer state for windows with their own VirtualDom
+pub fn use_timer_state() -> Signal<TimerState> {
+    let mut state = use_signal(|| TIMER_STATE.borrow().clone());
+
+    use_future(move || async move {
+        let mut receiver = TIMER_STATE.subscribe();
+        while receiver.changed().await.is_ok() {
+            let current = receiver.borrow_and_update().clone();
+            state.set(current);
+        }
+    });
+
+    state
+}
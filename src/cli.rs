@@ -0,0 +1,134 @@
+use crate::{
+    history::{
+        insert_record, parse_manual_session, read_history, save_history, SessionKind, SessionRecord,
+    },
+    ipc::{send_to_app, COMMANDS},
+};
+
+const USAGE: &str = "Usage:
+  AuraTimer send COMMAND
+  AuraTimer history list [COUNT]
+  AuraTimer history add KIND DATE START END [TASK]
+  AuraTimer history edit NUMBER KIND DATE START END [TASK]
+  AuraTimer history delete NUMBER
+
+KIND is focus, short_break or long_break, DATE looks like 2025-06-02 and START and END like 9:25.
+TASK may include @project and #tag. NUMBER is the one shown by list.";
+
+/// Runs `AuraTimer send …` and `AuraTimer history …` from a terminal instead of launching the app.
+/// `args` come without the program name, returns the exit code or `None` if the app should launch as usual.
+pub fn run(args: &[String]) -> Option<i32> {
+    let result = match args.first().map(String::as_str) {
+        Some("send") => send_command(&args[1..]),
+        Some("history") => history_command(&args[1..]),
+        _ => return None,
+    };
+
+    match result {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("{}", e);
+            Some(1)
+        }
+    }
+}
+
+/// Sends a command to the running app, e.g. `AuraTimer send toggle`
+fn send_command(args: &[String]) -> Result<(), String> {
+    if args.is_empty() {
+        return Err(format!("{}\n\nCOMMAND is one of {}.", USAGE, COMMANDS));
+    }
+
+    let reply = send_to_app(&args.join(" "))?;
+    if reply != "ok" {
+        return Err(reply);
+    }
+    Ok(())
+}
+
+fn history_command(args: &[String]) -> Result<(), String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    // a broken file mustn't be overwritten with just the new session
+    let mut history =
+        read_history().map_err(|e| format!("Failed to read session history: {}", e))?;
+
+    match args.as_slice() {
+        ["list"] => list(&history, 20),
+        ["list", count] => list(
+            &history,
+            count
+                .parse()
+                .map_err(|_| format!("\"{}\" isn't a number", count))?,
+        ),
+        ["add", kind, date, start, end, task @ ..] => {
+            let record =
+                parse_manual_session(parse_kind(kind)?, date, start, end, &task.join(" "))?;
+            insert_record(&mut history, record, None)?;
+            save(&history)?;
+        }
+        ["edit", number, kind, date, start, end, task @ ..] => {
+            let index = parse_number(number, &history)?;
+            let changes =
+                parse_manual_session(parse_kind(kind)?, date, start, end, &task.join(" "))?;
+            let record = history[index].edited(changes);
+            insert_record(&mut history, record, Some(index))?;
+            save(&history)?;
+        }
+        ["delete", number] => {
+            let index = parse_number(number, &history)?;
+            history.remove(index);
+            save(&history)?;
+        }
+        _ => return Err(USAGE.to_string()),
+    }
+    Ok(())
+}
+
+/// Prints the last `count` sessions, oldest first
+fn list(history: &[SessionRecord], count: usize) {
+    for (i, record) in history
+        .iter()
+        .enumerate()
+        .skip(history.len().saturating_sub(count))
+    {
+        let mut line = format!(
+            "{:>4}  {} – {}  {:<11}  {:>3}m",
+            i + 1,
+            record.started_at.format("%Y-%m-%d %H:%M"),
+            record.ended_at.format("%H:%M"),
+            record.kind.label(),
+            record.duration_secs / 60,
+        );
+        if !record.completed {
+            line.push_str("  skipped");
+        }
+        if !record.interruptions.is_empty() {
+            line.push_str(&format!("  {} interrupted", record.interruptions.len()));
+        }
+        if let Some(task) = &record.task {
+            line.push_str(&format!("  {}", task));
+        }
+        println!("{}", line);
+    }
+}
+
+fn parse_kind(kind: &str) -> Result<SessionKind, String> {
+    SessionKind::from_name(kind).ok_or(format!(
+        "Unknown session kind \"{}\", use focus, short_break or long_break",
+        kind
+    ))
+}
+
+/// Turns the 1-based number shown by `list` into an index
+fn parse_number(number: &str, history: &[SessionRecord]) -> Result<usize, String> {
+    number
+        .parse::<usize>()
+        .ok()
+        .filter(|n| (1..=history.len()).contains(n))
+        .map(|n| n - 1)
+        .ok_or(format!("There is no session number {}", number))
+}
+
+fn save(history: &[SessionRecord]) -> Result<(), String> {
+    save_history(history).map_err(|e| format!("Failed to save session history: {}", e))
+}
@@ -0,0 +1,66 @@
+use dioxus::prelude::*;
+
+use crate::{
+    components::timer::{toggle_timer, TIMER_RUNNING},
+    idle::AWAY_PROMPT,
+    settings::SETTINGS,
+    state::{BG_COLOR, TEXT_COLOR_INVERTED},
+    ui::button::Button,
+};
+
+fn resume_after_away() {
+    *AWAY_PROMPT.write() = None;
+    if !*TIMER_RUNNING.peek() {
+        toggle_timer();
+    }
+}
+
+fn dismiss_away_prompt() {
+    *AWAY_PROMPT.write() = None;
+}
+
+#[component]
+pub fn AwayPrompt() -> Element {
+    let bg_color = *BG_COLOR.read();
+    let text_color_inverted = *TEXT_COLOR_INVERTED.read();
+
+    let minutes = AWAY_PROMPT.read().map_or(0, |away| away.as_secs() / 60);
+    let subtracted = SETTINGS.read().subtract_idle_time;
+
+    rsx! {
+        div {
+            class: format!("absolute top-0 left-0 w-full h-full z-30 flex flex-col items-center justify-center text-center space-y-8 {}", bg_color),
+            h1 {
+                class: "text-6xl font-bold",
+                if minutes == 1 {
+                    "You were away for 1 minute"
+                } else {
+                    "You were away for {minutes} minutes"
+                }
+            }
+            p {
+                class: "text-2xl",
+                if subtracted {
+                    "The focus session was paused and the time away wasn't counted."
+                } else {
+                    "The focus session was paused."
+                }
+            }
+            div {
+                class: "flex space-x-4",
+                Button {
+                    title: "Resume the focus session",
+                    action: resume_after_away,
+                    class: format!("w-32 h-12 text-xl {}", text_color_inverted),
+                    text: "Resume",
+                }
+                Button {
+                    title: "Keep the session paused",
+                    action: dismiss_away_prompt,
+                    class: format!("w-32 h-12 text-xl {}", text_color_inverted),
+                    text: "Later",
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use dioxus::{
+    desktop::{tao::window::Fullscreen, window, Config, WindowBuilder},
+    prelude::*,
+};
+
+use crate::{
+    bus::{send_command, use_timer_state, TimerCommand},
+    components::timer::read_break_enforced,
+    settings::EmergencySkip,
+};
+
+const HOLD_TO_SKIP: Duration = Duration::from_secs(3);
+
+const SUGGESTIONS: [&str; 6] = [
+    "Stand up and stretch your legs.",
+    "Look at something far away for 20 seconds.",
+    "Drink a glass of water.",
+    "Take a short walk around the room.",
+    "Close your eyes and take a few deep breaths.",
+    "Roll your shoulders and relax your hands.",
+];
+
+/// Opens the break overlays when a strict break starts, they close themselves when it ends
+pub fn use_break_enforcement() {
+    let mut shown = use_signal(|| false);
+
+    use_effect(move || {
+        let enforced = read_break_enforced();
+
+        if enforced && !*shown.peek() {
+            show_break_overlays();
+            shown.set(true);
+        } else if !enforced {
+            shown.set(false);
+        }
+    });
+}
+
+fn show_break_overlays() {
+    let handle = window();
+
+    for monitor in handle.available_monitors() {
+        let config = Config::new()
+            .with_window(
+                WindowBuilder::new()
+                    .with_title("Break")
+                    .with_fullscreen(Some(Fullscreen::Borderless(Some(monitor))))
+                    .with_closable(false)
+                    .with_always_on_top(true)
+                    .with_decorations(false)
+                    .with_focused(true),
+            )
+            .with_disable_context_menu(true);
+        handle.new_window(VirtualDom::new(BreakOverlay), config);
+    }
+}
+
+#[component]
+pub fn BreakOverlay() -> Element {
+    let state = use_timer_state();
+    let mut seen_enforced = use_signal(|| false);
+    let mut hold_started = use_signal(|| None::<Instant>);
+    let mut phrase_input = use_signal(String::new);
+
+    // SETTINGS would be loaded again from disk in this window, the main window sends its own
+    let skip_method = state.read().emergency_skip;
+    let phrase = state.read().emergency_skip_phrase.clone();
+    let phrase_to_type = phrase.clone();
+    let suggestion = use_hook(|| {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        SUGGESTIONS[secs as usize % SUGGESTIONS.len()]
+    });
+
+    use_effect(move || {
+        if state.read().break_enforced {
+            seen_enforced.set(true);
+        } else if *seen_enforced.peek() {
+            // the break is over or was skipped
+            window().close();
+        }
+    });
+
+    use_future(move || async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(100));
+        loop {
+            interval.tick().await;
+
+            if hold_started
+                .peek()
+                .is_some_and(|started| started.elapsed() >= HOLD_TO_SKIP)
+            {
+                hold_started.set(None);
+                send_command(TimerCommand::EmergencySkip);
+            }
+        }
+    });
+
+    let millis_remaining = state.read().millis_remaining;
+    let minutes = millis_remaining / 1000 / 60;
+    let seconds = millis_remaining / 1000 % 60;
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: asset!("/assets/tailwind.css") }
+        div {
+            class: "w-screen h-screen bg-green-200 text-green-500 font-bold flex flex-col items-center justify-center space-y-8 outline-none select-none",
+            tabindex: "0",
+            autofocus: true,
+            onkeydown: move |e: KeyboardEvent| {
+                if skip_method == EmergencySkip::HoldKey
+                    && e.key() == Key::Escape
+                    && hold_started.peek().is_none()
+                {
+                    hold_started.set(Some(Instant::now()));
+                }
+            },
+            onkeyup: move |e: KeyboardEvent| {
+                if e.key() == Key::Escape {
+                    hold_started.set(None);
+                }
+            },
+            h1 {
+                class: "text-6xl",
+                "Time for a break"
+            }
+            p {
+                class: "text-[10rem]",
+                {format!("{:02}:{:02}", minutes, seconds)}
+            }
+            p {
+                class: "text-2xl",
+                {suggestion}
+            }
+            match skip_method {
+                EmergencySkip::HoldKey => rsx! {
+                    p {
+                        class: "text-xl font-normal",
+                        if let Some(started) = *hold_started.read() {
+                            {format!("Keep holding to skip… {}s", HOLD_TO_SKIP.saturating_sub(started.elapsed()).as_secs() + 1)}
+                        } else {
+                            {format!("Emergency? Hold Esc for {} seconds to skip this break.", HOLD_TO_SKIP.as_secs())}
+                        }
+                    }
+                },
+                EmergencySkip::TypePhrase => rsx! {
+                    p {
+                        class: "text-xl font-normal",
+                        "Emergency? Type \"{phrase_to_type}\" to skip this break."
+                    }
+                    input {
+                        r#type: "text",
+                        class: "rounded-lg px-2 py-1 w-96 text-xl font-normal text-center bg-green-500 text-green-200",
+                        value: "{phrase_input}",
+                        oninput: move |e: FormEvent| {
+                            if e.value().trim() == phrase.trim() {
+                                send_command(TimerCommand::EmergencySkip);
+                            }
+                            phrase_input.set(e.value());
+                        },
+                    }
+                },
+            }
+        }
+    }
+}
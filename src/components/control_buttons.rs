@@ -1,45 +1,54 @@
 use dioxus::prelude::*;
 
 use crate::{
-    components::timer::{clear_timer, next_session, revert_session, SKIPPED_SESSION},
+    components::timer::{next_session, redo, redo_action, restart_timer, undo, undo_action},
+    settings::SETTINGS,
+    state::PHASE_INDEX,
     ui::{icon_button::IconButton, icons::IconType},
 };
 
 #[component]
 pub fn ControlButtons() -> Element {
-    let left_button_icon = if *SKIPPED_SESSION.read() {
-        IconType::Revert
+    // in flowtime the focus session only ends through this button
+    let settings = SETTINGS.read();
+    let skip_title = if settings.flowtime && settings.phase(*PHASE_INDEX.read()).is_focus() {
+        "Finish focus"
     } else {
-        IconType::Restart
-    };
-
-    let left_button_action = if *SKIPPED_SESSION.read() {
-        revert_session
-    } else {
-        clear_timer
-    };
-
-    let left_button_title = if *SKIPPED_SESSION.read() {
-        "Restore previous session"
-    } else {
-        "Restart timer"
+        "Skip session"
     };
+    drop(settings);
 
     rsx! {
         div {
             class: "absolute bottom-0 left-0 flex items-end justify-center w-full h-1/5 py-4",
+            if let Some(action) = undo_action() {
+                IconButton {
+                    icon_type: IconType::Revert,
+                    title: format!("Undo {}", action.name()),
+                    size: "4rem",
+                    action: undo,
+                }
+            }
             IconButton {
-                icon_type: left_button_icon,
-                title: left_button_title,
+                icon_type: IconType::Restart,
+                title: "Restart timer",
                 size: "6rem",
-                action: left_button_action,
+                action: restart_timer,
             }
             IconButton {
                 icon_type: IconType::Skip,
-                title: "Skip session",
+                title: skip_title,
                 size: "6rem",
                 action: next_session,
             }
+            if let Some(action) = redo_action() {
+                IconButton {
+                    icon_type: IconType::Redo,
+                    title: format!("Redo {}", action.name()),
+                    size: "4rem",
+                    action: redo,
+                }
+            }
         }
     }
 }
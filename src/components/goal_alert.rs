@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use dioxus::{desktop::window, prelude::*};
+
+const SHOWN_FOR: Duration = Duration::from_secs(10);
+
+#[component]
+pub fn GoalAlert(summary: String) -> Element {
+    // nothing to decide, so it goes away by itself
+    use_future(|| async {
+        tokio::time::sleep(SHOWN_FOR).await;
+        window().close();
+    });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: asset!("/assets/tailwind.css") }
+        div {
+            class: "w-screen h-screen bg-green-200 text-green-500 font-bold text-4xl rounded-lg p-4 flex justify-between items-center",
+            h1 {
+                class: "truncate",
+                "Daily goal reached: {summary}"
+            }
+            button {
+                class: "transition duration-200 rounded-2xl z-10 cursor-default hover:cursor-pointer bg-green-500 text-green-200 w-32 h-12 text-xl",
+                title: "Close",
+                onclick: move |_| window().close(),
+                "Nice!"
+            }
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use dioxus::{desktop::window, prelude::*};
 
 use crate::{
-    state::{
-        BG_COLOR_INVERTED, ICON_COLOR, IS_FOCUS_MODE, SMALL_SESSION_COUNT, TEXT_COLOR_INVERTED,
-    },
+    components::{preset_picker::PresetPicker, task_label::TaskLabelInput},
+    goals::goal_progress,
+    interruptions::{can_log_interruption, open_interruption_prompt, INTERRUPTIONS},
+    settings::SETTINGS,
+    state::{BG_COLOR_INVERTED, ICON_COLOR, PHASE_INDEX, SMALL_SESSION_COUNT, TEXT_COLOR_INVERTED},
     ui::icons::{Icon, IconType},
 };
 
@@ -12,7 +14,7 @@ static HIDDEN_ITEMS: GlobalSignal<u32> = GlobalSignal::new(|| 0);
 
 #[component]
 pub fn Info() -> Element {
-    let is_focus_mode = *IS_FOCUS_MODE.read();
+    let phase = SETTINGS.read().phase(*PHASE_INDEX.read());
     let small_session_count = *SMALL_SESSION_COUNT.read();
 
     fn update_visible_sessions(small_session_count: u32) {
@@ -40,20 +42,19 @@ pub fn Info() -> Element {
 
     rsx! {
         div {
-            class: "absolute top-[-5rem] left-0 w-full h-16 text-2xl font-bold space-y-2 flex flex-col items-center justify-center text-center",
+            class: "absolute top-[-7rem] left-0 w-full h-24 text-2xl font-bold space-y-2 flex flex-col items-center justify-center text-center",
             onresize: move |_| {
                 update_visible_sessions(small_session_count);
             },
-            h1 {
-                if is_focus_mode {
-                    "Focus"
-                } else {
-                    if small_session_count % 4 == 0 && small_session_count != 0 {
-                        "Long break"
-                    } else {
-                        "Short break"
-                    }
+            div {
+                class: "flex items-center space-x-2",
+                h1 {
+                    {phase.name.clone()}
                 }
+                PresetPicker {}
+            }
+            if phase.is_focus() {
+                TaskLabelInput {}
             }
             SessionCount {}
         }
@@ -88,6 +89,27 @@ fn SessionCount() -> Element {
                     class: format!("transition-all duration-200 ease-out {}", color)
                 }
             }
+
+            if can_log_interruption() || !INTERRUPTIONS.read().is_empty() {
+                button {
+                    class: format!("text-xs px-2 py-1 rounded-2xl text-center flex justify-center items-center cursor-pointer {} {}", bg_color, text_color),
+                    title: "Log an interruption",
+                    onclick: move |_| open_interruption_prompt(),
+                    "Interruptions: {INTERRUPTIONS.read().len()}"
+                }
+            }
+
+            if let Some(progress) = goal_progress() {
+                div {
+                    class: format!("text-xs px-2 py-1 rounded-2xl text-center flex justify-center items-center {} {}", bg_color, text_color),
+                    title: "Daily goal",
+                    if progress.reached() {
+                        "✓ {progress.summary()} today"
+                    } else {
+                        "{progress.summary()} today"
+                    }
+                }
+            }
         }
     }
 }
@@ -0,0 +1,52 @@
+use dioxus::prelude::*;
+
+use crate::{
+    interruptions::{close_interruption_prompt, log_interruption, InterruptionKind},
+    state::{BG_COLOR, BG_COLOR_INVERTED, TEXT_COLOR_INVERTED},
+    ui::button::Button,
+};
+
+#[component]
+pub fn InterruptionPrompt() -> Element {
+    let mut note = use_signal(String::new);
+
+    let bg_color = *BG_COLOR.read();
+    let bg_color_inverted = *BG_COLOR_INVERTED.read();
+    let text_color_inverted = *TEXT_COLOR_INVERTED.read();
+
+    rsx! {
+        div {
+            class: format!("absolute top-0 left-0 w-full h-full z-30 flex flex-col items-center justify-center text-center space-y-8 {}", bg_color),
+            h1 {
+                class: "text-6xl font-bold",
+                "What interrupted you?"
+            }
+            input {
+                r#type: "text",
+                class: format!("w-full max-w-md rounded-lg px-2 py-1 text-xl {} {}", bg_color_inverted, text_color_inverted),
+                placeholder: "Note (optional)",
+                autofocus: true,
+                value: "{note}",
+                oninput: move |e: FormEvent| note.set(e.value()),
+            }
+            div {
+                class: "flex space-x-4",
+                // not `Button`s, their action can't carry the note
+                for kind in InterruptionKind::ALL {
+                    button {
+                        class: format!("transition duration-200 rounded-2xl z-10 cursor-default hover:cursor-pointer w-32 h-12 text-xl {} {}", bg_color_inverted, text_color_inverted),
+                        title: format!("Log an {} interruption", kind.label().to_lowercase()),
+                        onclick: move |_| log_interruption(kind, &note.peek()),
+                        {kind.label()}
+                    }
+                }
+                Button {
+                    title: "Don't log an interruption",
+                    action: close_interruption_prompt,
+                    class: format!("w-32 h-12 text-xl {}", text_color_inverted),
+                    text: "Cancel",
+                }
+            }
+        }
+    }
+}
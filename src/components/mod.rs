@@ -1,5 +1,18 @@
 pub mod alert;
+pub mod away_prompt;
+pub mod break_overlay;
 pub mod control_buttons;
+pub mod goal_alert;
 pub mod info;
+pub mod interruption_prompt;
+pub mod preset_picker;
+pub mod reminder_alert;
+pub mod reminder_list;
+pub mod settings_panel;
+pub mod shortcuts_overlay;
+pub mod stats_overlay;
+pub mod task_label;
+pub mod task_list;
 pub mod timer;
 pub mod timer_expired;
+pub mod work_start_prompt;
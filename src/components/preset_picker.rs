@@ -0,0 +1,43 @@
+use dioxus::prelude::*;
+
+use crate::settings::{switch_preset, SETTINGS};
+
+/// Shows the preset in use and switches to another one
+#[component]
+pub fn PresetPicker() -> Element {
+    let settings = SETTINGS.read();
+    let current = settings.current_preset().map(|preset| preset.name.clone());
+    let names: Vec<String> = settings
+        .presets
+        .iter()
+        .map(|preset| preset.name.clone())
+        .collect();
+    drop(settings);
+
+    if names.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        select {
+            class: "bg-transparent text-base font-normal cursor-pointer outline-none",
+            title: "Timer preset",
+            onchange: move |e: FormEvent| switch_preset(&e.value()),
+            if current.is_none() {
+                option {
+                    value: "",
+                    selected: true,
+                    disabled: true,
+                    "Custom"
+                }
+            }
+            for name in names {
+                option {
+                    value: "{name}",
+                    selected: current.as_ref() == Some(&name),
+                    "{name}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use dioxus::{desktop::window, prelude::*};
+
+use crate::{
+    bus::{send_command, ReminderCommand},
+    reminders::use_ringing,
+};
+
+#[component]
+pub fn ReminderAlert(id: u64, label: String) -> Element {
+    let ringing = use_ringing(id);
+    // the reminder may not be published as ringing yet when the window opens
+    let mut seen_ringing = use_signal(|| false);
+
+    use_effect(move || {
+        if *ringing.read() {
+            seen_ringing.set(true);
+        } else if *seen_ringing.peek() {
+            // dismissed in another window
+            window().close();
+        }
+    });
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: asset!("/assets/tailwind.css") }
+        div {
+            class: "w-screen h-screen bg-amber-200 text-amber-500 font-bold text-4xl rounded-lg p-4 flex justify-between items-center",
+            h1 {
+                class: "truncate",
+                {label}
+            }
+            // not a `Button`, its action can't carry the reminder id
+            button {
+                class: "transition duration-200 rounded-2xl z-10 cursor-default hover:cursor-pointer bg-amber-500 text-amber-200 w-32 h-12 text-xl",
+                title: "Dismiss reminder",
+                onclick: move |_| send_command(ReminderCommand::Dismiss(id)),
+                "Dismiss"
+            }
+        }
+    }
+}
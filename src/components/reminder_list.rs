@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use chrono::Local;
+use dioxus::prelude::*;
+
+use crate::{
+    reminders::{add_alarm, add_countdown, remove_reminder, Reminder, ReminderKind, REMINDERS},
+    state::{BG_COLOR, BG_COLOR_INVERTED, SHOW_REMINDERS, TEXT_COLOR_INVERTED},
+};
+
+pub fn toggle_reminders() {
+    let show_reminders = *SHOW_REMINDERS.peek();
+    *SHOW_REMINDERS.write() = !show_reminders;
+}
+
+/// "12" sets a countdown in minutes, "15:30" an alarm
+fn add_from_input(label: &str, when: &str) -> Result<(), String> {
+    let when = when.trim();
+    if when.contains(':') {
+        return add_alarm(label, when);
+    }
+    let minutes = when
+        .parse::<u32>()
+        .map_err(|_| "Enter minutes like 12 or a time like 15:30".to_string())?;
+    add_countdown(label, minutes)
+}
+
+#[component]
+pub fn ReminderPanel() -> Element {
+    let mut label = use_signal(String::new);
+    let mut when = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let bg_color = *BG_COLOR.read();
+    let input_bg_color = *BG_COLOR_INVERTED.read();
+    let input_text_color = *TEXT_COLOR_INVERTED.read();
+
+    let reminders = REMINDERS.read().clone();
+
+    let mut submit = move || {
+        let result = add_from_input(&label.peek(), &when.peek());
+        match result {
+            Ok(()) => {
+                label.set(String::new());
+                when.set(String::new());
+                error.set(None);
+            }
+            Err(e) => error.set(Some(e)),
+        }
+    };
+
+    rsx! {
+        div {
+            class: format!("absolute top-0 left-0 w-full h-full z-20 pt-12 px-8 pb-8 overflow-y-auto flex flex-col items-center {}", bg_color),
+            div {
+                class: "w-full max-w-lg space-y-6",
+                div {
+                    class: "flex items-center justify-between",
+                    h1 {
+                        class: "text-4xl font-bold",
+                        "Reminders"
+                    }
+                    button {
+                        class: "text-xl font-bold cursor-pointer",
+                        title: "Close reminders",
+                        onclick: move |_| *SHOW_REMINDERS.write() = false,
+                        "Done"
+                    }
+                }
+                div {
+                    class: "flex items-center space-x-2",
+                    input {
+                        r#type: "text",
+                        class: format!("flex-grow rounded-lg px-2 py-1 text-xl {} {}", input_bg_color, input_text_color),
+                        placeholder: "Label",
+                        value: "{label}",
+                        oninput: move |e: FormEvent| label.set(e.value()),
+                        onkeydown: move |e: KeyboardEvent| {
+                            if e.key() == Key::Enter {
+                                submit();
+                            }
+                        },
+                    }
+                    input {
+                        r#type: "text",
+                        class: format!("rounded-lg px-2 py-1 w-28 text-xl text-right {} {}", input_bg_color, input_text_color),
+                        title: "Minutes from now, or a time of day",
+                        placeholder: "12 or 15:30",
+                        value: "{when}",
+                        oninput: move |e: FormEvent| when.set(e.value()),
+                        onkeydown: move |e: KeyboardEvent| {
+                            if e.key() == Key::Enter {
+                                submit();
+                            }
+                        },
+                    }
+                    button {
+                        class: "text-xl font-bold cursor-pointer",
+                        title: "Add reminder",
+                        onclick: move |_| submit(),
+                        "Add"
+                    }
+                }
+                if let Some(error) = error.read().as_ref() {
+                    p {
+                        class: "font-bold",
+                        "{error}"
+                    }
+                }
+                if reminders.is_empty() {
+                    p { "No reminders" }
+                }
+                for reminder in reminders {
+                    ReminderRow { key: "{reminder.id}", reminder: reminder.clone() }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ReminderRow(reminder: Reminder) -> Element {
+    let mut now = use_signal(Local::now);
+    let id = reminder.id;
+
+    // countdowns show the time left
+    use_future(move || async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            now.set(Local::now());
+        }
+    });
+
+    let when = if reminder.fired {
+        "Ringing".to_string()
+    } else {
+        match reminder.kind {
+            ReminderKind::Countdown => {
+                let secs = (reminder.due - *now.read()).num_seconds().max(0);
+                format!("in {:02}:{:02}", secs / 60, secs % 60)
+            }
+            ReminderKind::Alarm => format!("at {}", reminder.due.format("%H:%M")),
+        }
+    };
+
+    rsx! {
+        div {
+            class: "flex items-center space-x-2 text-xl",
+            span {
+                class: "flex-grow truncate",
+                {reminder.label.clone()}
+            }
+            span {
+                class: if reminder.fired { "font-bold" } else { "font-mono" },
+                {when}
+            }
+            button {
+                class: "cursor-pointer",
+                title: if reminder.fired { "Dismiss" } else { "Cancel" },
+                onclick: move |_| remove_reminder(id),
+                "✕"
+            }
+        }
+    }
+}
@@ -0,0 +1,809 @@
+use chrono::NaiveTime;
+use dioxus::prelude::*;
+
+use crate::{
+    bus::SessionEventKind,
+    history::SessionKind,
+    hotkeys::{GlobalHotkeyAction, HOTKEY_ERRORS},
+    settings::{
+        default_working_hours, update_settings, AlertDisplay, AlertPosition, DailyGoal,
+        EmergencySkip, Phase, PhaseColor, Settings, Theme, WorkBlock, SETTINGS,
+    },
+    shortcuts::ShortcutAction,
+    state::{BG_COLOR_INVERTED, SHOW_SETTINGS, TEXT_COLOR_INVERTED},
+    ui::button::Button,
+};
+
+pub fn open_settings() {
+    *SHOW_SETTINGS.write() = true;
+}
+
+pub fn close_settings() {
+    *SHOW_SETTINGS.write() = false;
+}
+
+pub fn toggle_settings() {
+    let show_settings = *SHOW_SETTINGS.peek();
+    *SHOW_SETTINGS.write() = !show_settings;
+}
+
+#[component]
+pub fn SettingsPanel() -> Element {
+    // edits are kept in a draft so invalid values can be shown while typing without applying them
+    let mut draft = use_signal(|| SETTINGS.peek().clone());
+    let mut error = use_signal(|| None::<String>);
+    let mut new_preset = use_signal(String::new);
+
+    let mut edit = move |apply: &dyn Fn(&mut Settings)| {
+        apply(&mut draft.write());
+
+        let settings = draft.read().clone();
+        match settings.validate() {
+            Ok(()) => {
+                error.set(None);
+                update_settings(settings);
+            }
+            Err(e) => error.set(Some(e)),
+        }
+    };
+
+    let mut edit_number = move |value: Option<u32>, apply: fn(&mut Settings, u32)| match value {
+        Some(value) => edit(&|settings| apply(settings, value)),
+        None => error.set(Some("Please enter a whole number".to_string())),
+    };
+
+    let settings = draft.read().clone();
+    let text_color_inverted = *TEXT_COLOR_INVERTED.read();
+
+    rsx! {
+        div {
+            class: "absolute top-0 left-0 w-full h-full pt-12 px-8 pb-8 overflow-y-auto flex flex-col items-center",
+            div {
+                class: "w-full max-w-md space-y-6",
+                h1 {
+                    class: "text-4xl font-bold",
+                    "Settings"
+                }
+                SettingsSection {
+                    title: "Durations",
+                    NumberSetting {
+                        label: "Focus (minutes)",
+                        value: settings.focus_minutes,
+                        min: 1,
+                        max: 180,
+                        onchange: move |value| edit_number(value, |s, v| s.focus_minutes = v),
+                    }
+                    NumberSetting {
+                        label: "Short break (minutes)",
+                        value: settings.short_break_minutes,
+                        min: 1,
+                        max: 60,
+                        onchange: move |value| edit_number(value, |s, v| s.short_break_minutes = v),
+                    }
+                    NumberSetting {
+                        label: "Long break (minutes)",
+                        value: settings.long_break_minutes,
+                        min: 1,
+                        max: 120,
+                        onchange: move |value| edit_number(value, |s, v| s.long_break_minutes = v),
+                    }
+                    NumberSetting {
+                        label: "Sessions before long break",
+                        value: settings.long_break_interval,
+                        min: 1,
+                        max: 12,
+                        onchange: move |value| edit_number(value, |s, v| s.long_break_interval = v),
+                    }
+                    ToggleSetting {
+                        label: "Count focus up (flowtime)",
+                        checked: settings.flowtime,
+                        onchange: move |checked| edit(&|s| s.flowtime = checked),
+                    }
+                    if settings.flowtime {
+                        NumberSetting {
+                            label: "Break (% of focus time)",
+                            value: settings.flowtime_break_percent,
+                            min: 5,
+                            max: 100,
+                            onchange: move |value| edit_number(value, |s, v| s.flowtime_break_percent = v),
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Sequence",
+                    if settings.sequence.is_empty() {
+                        p {
+                            "Focus and short breaks with a long break after every {settings.long_break_interval} sessions, using the durations above."
+                        }
+                    }
+                    for (i, phase) in settings.sequence.iter().cloned().enumerate() {
+                        PhaseEditor {
+                            key: "{i}",
+                            phase,
+                            onchange: move |phase: Phase| edit(&|s| s.sequence[i] = phase.clone()),
+                            onremove: move |_| {
+                                edit(&|s| {
+                                    s.sequence.remove(i);
+                                })
+                            },
+                        }
+                    }
+                    div {
+                        class: "flex space-x-4 text-xl font-bold",
+                        if settings.sequence.is_empty() {
+                            button {
+                                class: "cursor-pointer",
+                                title: "Start a custom sequence from the classic one",
+                                onclick: move |_| edit(&|s| s.sequence = s.phases()),
+                                "Customize"
+                            }
+                        } else {
+                            button {
+                                class: "cursor-pointer",
+                                onclick: move |_| edit(&|s| s.sequence.push(Phase::default())),
+                                "Add phase"
+                            }
+                            button {
+                                class: "cursor-pointer",
+                                onclick: move |_| edit(&|s| s.sequence.clear()),
+                                "Back to classic"
+                            }
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Presets",
+                    for preset in settings.presets.iter().cloned() {
+                        div {
+                            key: "{preset.name}",
+                            class: "flex items-center justify-between text-xl",
+                            span {
+                                class: if settings.current_preset() == Some(&preset) { "font-bold" } else { "" },
+                                "{preset.name}"
+                            }
+                            div {
+                                class: "flex space-x-4",
+                                button {
+                                    class: "cursor-pointer",
+                                    title: "Use these durations",
+                                    onclick: {
+                                        let name = preset.name.clone();
+                                        move |_| edit(&|s| s.use_preset(&name))
+                                    },
+                                    "Use"
+                                }
+                                button {
+                                    class: "cursor-pointer",
+                                    title: "Delete preset",
+                                    onclick: {
+                                        let name = preset.name.clone();
+                                        move |_| edit(&|s| s.presets.retain(|p| p.name != name))
+                                    },
+                                    "✕"
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        class: "flex items-center space-x-2 text-xl",
+                        input {
+                            r#type: "text",
+                            class: format!("flex-grow rounded-lg px-2 py-1 {} {}", *BG_COLOR_INVERTED.read(), text_color_inverted),
+                            placeholder: "Preset name",
+                            value: "{new_preset}",
+                            oninput: move |e: FormEvent| new_preset.set(e.value()),
+                        }
+                        button {
+                            class: "font-bold cursor-pointer",
+                            title: "Save the durations and sequence above as a preset",
+                            onclick: move |_| {
+                                let name = new_preset.peek().clone();
+                                edit(&|s| s.save_preset(&name));
+                                new_preset.set(String::new());
+                            },
+                            "Save"
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Sounds",
+                    ToggleSetting {
+                        label: "Play alarm",
+                        checked: settings.play_sound,
+                        onchange: move |checked| edit(&|s| s.play_sound = checked),
+                    }
+                    NumberSetting {
+                        label: "Volume (%)",
+                        value: settings.sound_volume,
+                        min: 0,
+                        max: 100,
+                        onchange: move |value| edit_number(value, |s, v| s.sound_volume = v),
+                    }
+                }
+                SettingsSection {
+                    title: "Auto-start",
+                    ToggleSetting {
+                        label: "Start breaks automatically",
+                        checked: settings.auto_start_breaks,
+                        onchange: move |checked| edit(&|s| s.auto_start_breaks = checked),
+                    }
+                    ToggleSetting {
+                        label: "Start focus automatically",
+                        checked: settings.auto_start_focus,
+                        onchange: move |checked| edit(&|s| s.auto_start_focus = checked),
+                    }
+                }
+                SettingsSection {
+                    title: "Idle",
+                    ToggleSetting {
+                        label: "Pause focus when away",
+                        checked: settings.idle_pause,
+                        onchange: move |checked| edit(&|s| s.idle_pause = checked),
+                    }
+                    NumberSetting {
+                        label: "Away after (minutes)",
+                        value: settings.idle_minutes,
+                        min: 1,
+                        max: 60,
+                        onchange: move |value| edit_number(value, |s, v| s.idle_minutes = v),
+                    }
+                    ToggleSetting {
+                        label: "Don't count time away",
+                        checked: settings.subtract_idle_time,
+                        onchange: move |checked| edit(&|s| s.subtract_idle_time = checked),
+                    }
+                    if cfg!(target_os = "linux") {
+                        ToggleSetting {
+                            label: "Take breaks while the screen is locked",
+                            checked: settings.break_on_lock,
+                            onchange: move |checked| edit(&|s| s.break_on_lock = checked),
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Working hours",
+                    ToggleSetting {
+                        label: "Only run sessions during working hours",
+                        checked: settings.use_working_hours,
+                        onchange: move |checked| edit(&|s| s.use_working_hours = checked),
+                    }
+                    if settings.use_working_hours {
+                        for (i, block) in settings.working_hours.iter().cloned().enumerate() {
+                            WorkBlockEditor {
+                                key: "{i}",
+                                block,
+                                onchange: move |block: WorkBlock| edit(&|s| s.working_hours[i] = block.clone()),
+                                onremove: move |_| {
+                                    edit(&|s| {
+                                        s.working_hours.remove(i);
+                                    })
+                                },
+                            }
+                        }
+                        button {
+                            class: "cursor-pointer text-xl font-bold",
+                            onclick: move |_| {
+                                edit(&|s| {
+                                    let block = s
+                                        .working_hours
+                                        .last()
+                                        .cloned()
+                                        .unwrap_or_else(|| default_working_hours()[0].clone());
+                                    s.working_hours.push(block);
+                                })
+                            },
+                            "Add block"
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Daily goal",
+                    SelectSetting {
+                        label: "Goal",
+                        value: settings.daily_goal.name(),
+                        options: DailyGoal::ALL.map(|goal| goal.name()).to_vec(),
+                        onchange: move |name: String| {
+                            if let Some(goal) = DailyGoal::ALL.into_iter().find(|g| g.name() == name) {
+                                edit(&|s| s.daily_goal = goal);
+                            }
+                        },
+                    }
+                    if settings.daily_goal == DailyGoal::Sessions {
+                        NumberSetting {
+                            label: "Sessions per day",
+                            value: settings.daily_goal_sessions,
+                            min: 1,
+                            max: 50,
+                            onchange: move |value| edit_number(value, |s, v| s.daily_goal_sessions = v),
+                        }
+                    }
+                    if settings.daily_goal == DailyGoal::Minutes {
+                        NumberSetting {
+                            label: "Minutes per day",
+                            value: settings.daily_goal_minutes,
+                            min: 1,
+                            max: 1440,
+                            onchange: move |value| edit_number(value, |s, v| s.daily_goal_minutes = v),
+                        }
+                    }
+                    NumberSetting {
+                        label: "New day starts at (hour)",
+                        value: settings.day_start_hour,
+                        min: 0,
+                        max: 23,
+                        onchange: move |value| edit_number(value, |s, v| s.day_start_hour = v),
+                    }
+                    ToggleSetting {
+                        label: "Reset session counts every day",
+                        checked: settings.reset_count_daily,
+                        onchange: move |checked| edit(&|s| s.reset_count_daily = checked),
+                    }
+                }
+                SettingsSection {
+                    title: "Appearance",
+                    SelectSetting {
+                        label: "Theme",
+                        value: settings.theme.name(),
+                        options: Theme::ALL.map(|theme| theme.name()).to_vec(),
+                        onchange: move |name: String| {
+                            if let Some(theme) = Theme::ALL.into_iter().find(|t| t.name() == name) {
+                                edit(&|s| s.theme = theme);
+                            }
+                        },
+                    }
+                }
+                SettingsSection {
+                    title: "Notifications",
+                    ToggleSetting {
+                        label: "Show alert window",
+                        checked: settings.show_alert_window,
+                        onchange: move |checked| edit(&|s| s.show_alert_window = checked),
+                    }
+                    SelectSetting {
+                        label: "Alert on",
+                        value: settings.alert_display.name(),
+                        options: AlertDisplay::ALL.map(|display| display.name()).to_vec(),
+                        onchange: move |name: String| {
+                            if let Some(display) = AlertDisplay::ALL.into_iter().find(|d| d.name() == name) {
+                                edit(&|s| s.alert_display = display);
+                            }
+                        },
+                    }
+                    SelectSetting {
+                        label: "Alert position",
+                        value: settings.alert_position.name(),
+                        options: AlertPosition::ALL.map(|position| position.name()).to_vec(),
+                        onchange: move |name: String| {
+                            if let Some(position) = AlertPosition::ALL.into_iter().find(|p| p.name() == name) {
+                                edit(&|s| s.alert_position = position);
+                            }
+                        },
+                    }
+                    ToggleSetting {
+                        label: "Show countdown in menu bar",
+                        checked: settings.show_tray_countdown,
+                        onchange: move |checked| edit(&|s| s.show_tray_countdown = checked),
+                    }
+                }
+                if cfg!(target_os = "linux") {
+                    SettingsSection {
+                        title: "Media",
+                        ToggleSetting {
+                            label: "Pause media during focus",
+                            checked: settings.pause_media_during_focus,
+                            onchange: move |checked| edit(&|s| s.pause_media_during_focus = checked),
+                        }
+                        ToggleSetting {
+                            label: "Pause media during breaks",
+                            checked: settings.pause_media_during_breaks,
+                            onchange: move |checked| edit(&|s| s.pause_media_during_breaks = checked),
+                        }
+                        p {
+                            "Players paused by AuraTimer are resumed when the session ends or is paused."
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Strict breaks",
+                    ToggleSetting {
+                        label: "Cover all displays during breaks",
+                        checked: settings.strict_breaks,
+                        onchange: move |checked| edit(&|s| s.strict_breaks = checked),
+                    }
+                    SelectSetting {
+                        label: "Emergency skip",
+                        value: settings.emergency_skip.name(),
+                        options: EmergencySkip::ALL.map(|skip| skip.name()).to_vec(),
+                        onchange: move |name: String| {
+                            if let Some(skip) = EmergencySkip::ALL.into_iter().find(|s| s.name() == name) {
+                                edit(&|s| s.emergency_skip = skip);
+                            }
+                        },
+                    }
+                    if settings.emergency_skip == EmergencySkip::TypePhrase {
+                        TextSetting {
+                            label: "Phrase",
+                            value: settings.emergency_skip_phrase.clone(),
+                            onchange: move |phrase: String| edit(&|s| s.emergency_skip_phrase = phrase.clone()),
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Tasks",
+                    TextSetting {
+                        label: "Import from file",
+                        value: settings.task_source.clone(),
+                        onchange: move |path: String| edit(&|s| s.task_source = path.trim().to_string()),
+                    }
+                    p {
+                        "A todo.txt file or a Markdown file with - [ ] checkboxes. Tasks checked off in AuraTimer are checked off in the file too."
+                    }
+                }
+                SettingsSection {
+                    title: "Keyboard shortcuts",
+                    for action in ShortcutAction::ALL {
+                        TextSetting {
+                            label: action.description(),
+                            value: settings.shortcut(action).to_string(),
+                            onchange: move |binding: String| {
+                                edit(&|s| {
+                                    s.shortcuts.insert(action, binding.trim().to_string());
+                                })
+                            },
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Global hotkeys",
+                    for action in GlobalHotkeyAction::ALL {
+                        TextSetting {
+                            label: action.description(),
+                            value: settings.global_hotkey(action).to_string(),
+                            onchange: move |binding: String| {
+                                edit(&|s| {
+                                    s.global_hotkeys.insert(action, binding.trim().to_string());
+                                })
+                            },
+                        }
+                    }
+                    p {
+                        "Work even when the window is hidden. Leave empty to disable."
+                    }
+                    for hotkey_error in HOTKEY_ERRORS.read().iter() {
+                        p {
+                            class: "font-bold",
+                            "{hotkey_error}"
+                        }
+                    }
+                }
+                SettingsSection {
+                    title: "Event hooks",
+                    for kind in SessionEventKind::ALL {
+                        TextSetting {
+                            label: kind.name(),
+                            value: settings.hook(kind).to_string(),
+                            onchange: move |command: String| {
+                                edit(&|s| {
+                                    if command.trim().is_empty() {
+                                        s.hooks.remove(&kind);
+                                    } else {
+                                        s.hooks.insert(kind, command.clone());
+                                    }
+                                })
+                            },
+                        }
+                    }
+                    NumberSetting {
+                        label: "Timeout (seconds)",
+                        value: settings.hook_timeout_secs,
+                        min: 1,
+                        max: 300,
+                        onchange: move |value| edit_number(value, |s, v| s.hook_timeout_secs = v),
+                    }
+                    p {
+                        "Shell commands run when a session starts, ends, is paused, resumed or skipped. They get AURATIMER_EVENT, AURATIMER_PHASE, AURATIMER_PHASE_NAME, AURATIMER_PLANNED_SECONDS, AURATIMER_REMAINING_SECONDS, AURATIMER_SESSION_NUMBER and AURATIMER_TASK in their environment."
+                    }
+                }
+                SettingsSection {
+                    title: "Webhooks",
+                    TextAreaSetting {
+                        label: "URLs, one per line",
+                        value: settings.webhooks.join("\n"),
+                        onchange: move |urls: String| {
+                            edit(&|s| s.webhooks = urls.split('\n').map(str::to_string).collect())
+                        },
+                    }
+                    p {
+                        "Each URL gets a JSON POST for every session event. Failed deliveries are retried with increasing delays, even after a restart."
+                    }
+                }
+                if let Some(error) = error.read().as_ref() {
+                    p {
+                        class: "text-xl font-bold",
+                        "{error}"
+                    }
+                }
+                Button {
+                    title: "Close settings",
+                    action: close_settings,
+                    class: format!("w-32 h-12 text-xl {}", text_color_inverted),
+                    text: "Done",
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SettingsSection(title: String, children: Element) -> Element {
+    rsx! {
+        div {
+            class: "space-y-2",
+            h2 {
+                class: "text-2xl font-bold",
+                {title}
+            }
+            {children}
+        }
+    }
+}
+
+/// Name, duration, kind and color of one phase in a single row
+#[component]
+fn PhaseEditor(phase: Phase, onchange: EventHandler<Phase>, onremove: EventHandler<()>) -> Element {
+    let bg_color = *BG_COLOR_INVERTED.read();
+    let text_color = *TEXT_COLOR_INVERTED.read();
+    let input_class = format!("rounded-lg px-2 py-1 {} {}", bg_color, text_color);
+
+    let name_phase = phase.clone();
+    let minutes_phase = phase.clone();
+    let kind_phase = phase.clone();
+    let color_phase = phase.clone();
+
+    rsx! {
+        div {
+            class: "flex items-center space-x-2 text-xl",
+            input {
+                r#type: "text",
+                class: format!("flex-grow min-w-0 {}", input_class),
+                title: "Name",
+                value: "{phase.name}",
+                oninput: move |e: FormEvent| {
+                    onchange.call(Phase { name: e.value(), ..name_phase.clone() })
+                },
+            }
+            input {
+                r#type: "number",
+                class: format!("w-16 text-right {}", input_class),
+                title: "Minutes",
+                min: "1",
+                max: "240",
+                value: "{phase.minutes}",
+                oninput: move |e: FormEvent| {
+                    if let Ok(minutes) = e.value().trim().parse() {
+                        onchange.call(Phase { minutes, ..minutes_phase.clone() });
+                    }
+                },
+            }
+            select {
+                class: input_class.clone(),
+                title: "Kind",
+                onchange: move |e: FormEvent| {
+                    if let Some(kind) = SessionKind::ALL.into_iter().find(|k| k.label() == e.value()) {
+                        onchange.call(Phase { kind, ..kind_phase.clone() });
+                    }
+                },
+                for kind in SessionKind::ALL {
+                    option {
+                        value: kind.label(),
+                        selected: kind == phase.kind,
+                        {kind.label()}
+                    }
+                }
+            }
+            select {
+                class: input_class.clone(),
+                title: "Color",
+                onchange: move |e: FormEvent| {
+                    if let Some(color) = PhaseColor::ALL.into_iter().find(|c| c.name() == e.value()) {
+                        onchange.call(Phase { color, ..color_phase.clone() });
+                    }
+                },
+                for color in PhaseColor::ALL {
+                    option {
+                        value: color.name(),
+                        selected: color == phase.color,
+                        {color.name()}
+                    }
+                }
+            }
+            button {
+                class: "cursor-pointer font-bold",
+                title: "Remove phase",
+                onclick: move |_| onremove.call(()),
+                "✕"
+            }
+        }
+    }
+}
+
+#[component]
+fn WorkBlockEditor(
+    block: WorkBlock,
+    onchange: EventHandler<WorkBlock>,
+    onremove: EventHandler<()>,
+) -> Element {
+    let bg_color = *BG_COLOR_INVERTED.read();
+    let text_color = *TEXT_COLOR_INVERTED.read();
+    let input_class = format!("rounded-lg px-2 py-1 {} {}", bg_color, text_color);
+
+    let start = block.start.format("%H:%M").to_string();
+    let end = block.end.format("%H:%M").to_string();
+    let start_block = block.clone();
+    let end_block = block.clone();
+
+    rsx! {
+        div {
+            class: "flex items-center space-x-2 text-xl",
+            for (day, name) in WorkBlock::DAY_NAMES.into_iter().enumerate() {
+                label {
+                    key: "{day}",
+                    class: "flex flex-col items-center text-sm",
+                    span { {name} }
+                    input {
+                        r#type: "checkbox",
+                        class: "w-4 h-4",
+                        checked: block.days[day],
+                        onchange: {
+                            let block = block.clone();
+                            move |e: FormEvent| {
+                                let mut days = block.days;
+                                days[day] = e.checked();
+                                onchange.call(WorkBlock { days, ..block.clone() });
+                            }
+                        },
+                    }
+                }
+            }
+            input {
+                r#type: "time",
+                class: input_class.clone(),
+                title: "Start",
+                value: start,
+                onchange: move |e: FormEvent| {
+                    if let Ok(start) = NaiveTime::parse_from_str(&e.value(), "%H:%M") {
+                        onchange.call(WorkBlock { start, ..start_block.clone() });
+                    }
+                },
+            }
+            span { "–" }
+            input {
+                r#type: "time",
+                class: input_class.clone(),
+                title: "End",
+                value: end,
+                onchange: move |e: FormEvent| {
+                    if let Ok(end) = NaiveTime::parse_from_str(&e.value(), "%H:%M") {
+                        onchange.call(WorkBlock { end, ..end_block.clone() });
+                    }
+                },
+            }
+            button {
+                class: "cursor-pointer font-bold",
+                title: "Remove block",
+                onclick: move |_| onremove.call(()),
+                "✕"
+            }
+        }
+    }
+}
+
+#[component]
+fn NumberSetting(
+    label: String,
+    value: u32,
+    min: u32,
+    max: u32,
+    onchange: EventHandler<Option<u32>>,
+) -> Element {
+    let bg_color = *BG_COLOR_INVERTED.read();
+    let text_color = *TEXT_COLOR_INVERTED.read();
+
+    rsx! {
+        label {
+            class: "flex items-center justify-between text-xl",
+            span { {label} }
+            input {
+                r#type: "number",
+                class: format!("rounded-lg px-2 py-1 w-24 text-right {} {}", bg_color, text_color),
+                min: "{min}",
+                max: "{max}",
+                value: "{value}",
+                oninput: move |e: FormEvent| onchange.call(e.value().trim().parse().ok()),
+            }
+        }
+    }
+}
+
+#[component]
+fn TextSetting(label: String, value: String, onchange: EventHandler<String>) -> Element {
+    let bg_color = *BG_COLOR_INVERTED.read();
+    let text_color = *TEXT_COLOR_INVERTED.read();
+
+    rsx! {
+        label {
+            class: "flex items-center justify-between text-xl",
+            span { {label} }
+            input {
+                r#type: "text",
+                class: format!("rounded-lg px-2 py-1 w-48 text-right {} {}", bg_color, text_color),
+                value: "{value}",
+                oninput: move |e: FormEvent| onchange.call(e.value()),
+            }
+        }
+    }
+}
+
+#[component]
+fn TextAreaSetting(label: String, value: String, onchange: EventHandler<String>) -> Element {
+    let bg_color = *BG_COLOR_INVERTED.read();
+    let text_color = *TEXT_COLOR_INVERTED.read();
+
+    rsx! {
+        label {
+            class: "flex flex-col space-y-1 text-xl",
+            span { {label} }
+            textarea {
+                class: format!("rounded-lg px-2 py-1 h-24 text-base {} {}", bg_color, text_color),
+                value: "{value}",
+                oninput: move |e: FormEvent| onchange.call(e.value()),
+            }
+        }
+    }
+}
+
+#[component]
+fn SelectSetting(
+    label: String,
+    value: String,
+    options: Vec<&'static str>,
+    onchange: EventHandler<String>,
+) -> Element {
+    let bg_color = *BG_COLOR_INVERTED.read();
+    let text_color = *TEXT_COLOR_INVERTED.read();
+
+    rsx! {
+        label {
+            class: "flex items-center justify-between text-xl",
+            span { {label} }
+            select {
+                class: format!("rounded-lg px-2 py-1 {} {}", bg_color, text_color),
+                value: "{value}",
+                onchange: move |e: FormEvent| onchange.call(e.value()),
+                for option in options {
+                    option {
+                        value: option,
+                        selected: option == value,
+                        {option}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ToggleSetting(label: String, checked: bool, onchange: EventHandler<bool>) -> Element {
+    rsx! {
+        label {
+            class: "flex items-center justify-between text-xl",
+            span { {label} }
+            input {
+                r#type: "checkbox",
+                class: "w-6 h-6",
+                checked: checked,
+                onchange: move |e: FormEvent| onchange.call(e.checked()),
+            }
+        }
+    }
+}
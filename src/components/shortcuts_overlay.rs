@@ -0,0 +1,47 @@
+use dioxus::prelude::*;
+
+use crate::{
+    settings::SETTINGS,
+    shortcuts::ShortcutAction,
+    state::{BG_COLOR, BG_COLOR_INVERTED, SHOW_SHORTCUTS, TEXT_COLOR_INVERTED},
+};
+
+pub fn toggle_shortcuts() {
+    let show_shortcuts = *SHOW_SHORTCUTS.peek();
+    *SHOW_SHORTCUTS.write() = !show_shortcuts;
+}
+
+#[component]
+pub fn ShortcutsOverlay() -> Element {
+    let bg_color = *BG_COLOR.read();
+    let key_bg_color = *BG_COLOR_INVERTED.read();
+    let key_text_color = *TEXT_COLOR_INVERTED.read();
+
+    rsx! {
+        div {
+            class: format!("absolute top-0 left-0 w-full h-full z-20 flex items-center justify-center {}", bg_color),
+            onclick: move |_| *SHOW_SHORTCUTS.write() = false,
+            div {
+                class: "w-full max-w-md space-y-4",
+                h1 {
+                    class: "text-4xl font-bold text-center",
+                    "Keyboard shortcuts"
+                }
+                for action in ShortcutAction::ALL {
+                    div {
+                        class: "flex items-center justify-between text-xl",
+                        span { {action.description()} }
+                        span {
+                            class: format!("rounded-lg px-2 py-1 font-mono {} {}", key_bg_color, key_text_color),
+                            {SETTINGS.read().shortcut(action).to_string()}
+                        }
+                    }
+                }
+                p {
+                    class: "text-center",
+                    "Shortcuts can be changed in the settings. Press Esc to close."
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use chrono::{Local, TimeDelta};
+use dioxus::prelude::*;
+
+use crate::{
+    history::{
+        add_manual_record, edit_record, parse_manual_session, remove_record, stats_by_task,
+        SessionKind, SessionRecord, TaskStats, HISTORY,
+    },
+    state::{BG_COLOR, BG_COLOR_INVERTED, SHOW_STATS, TEXT_COLOR_INVERTED},
+};
+
+// sessions listed for editing, most recent first
+const RECENT_SESSIONS: usize = 15;
+
+pub fn toggle_stats() {
+    let show_stats = *SHOW_STATS.peek();
+    *SHOW_STATS.write() = !show_stats;
+}
+
+fn format_duration(secs: u32) -> String {
+    let minutes = secs / 60;
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+#[component]
+pub fn StatsOverlay() -> Element {
+    let bg_color = *BG_COLOR.read();
+
+    let today = Local::now().date_naive();
+    let history = HISTORY.read();
+    let today_stats = stats_by_task(
+        history
+            .iter()
+            .filter(|record| record.started_at.date_naive() == today),
+    );
+    let all_time_stats = stats_by_task(history.iter());
+
+    rsx! {
+        div {
+            class: format!("absolute top-0 left-0 w-full h-full z-20 pt-12 px-8 pb-8 overflow-y-auto flex flex-col items-center {}", bg_color),
+            onclick: move |_| *SHOW_STATS.write() = false,
+            div {
+                class: "w-full max-w-md space-y-6",
+                h1 {
+                    class: "text-4xl font-bold text-center",
+                    "Statistics"
+                }
+                TaskStatsList { title: "Today", stats: today_stats }
+                TaskStatsList { title: "All time", stats: all_time_stats }
+                SessionList {}
+                p {
+                    class: "text-center",
+                    "Click anywhere to close."
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TaskStatsList(title: String, stats: Vec<TaskStats>) -> Element {
+    rsx! {
+        div {
+            class: "space-y-2",
+            h2 {
+                class: "text-2xl font-bold",
+                {title}
+            }
+            if stats.is_empty() {
+                p { "No focus sessions yet" }
+            }
+            for task_stats in stats {
+                div {
+                    class: "flex items-center justify-between text-xl",
+                    span {
+                        {task_stats.task.map(|task| task.to_string()).unwrap_or("No task".to_string())}
+                    }
+                    span {
+                        if task_stats.interruptions > 0 {
+                            {format!("{} · {} sessions · {} interruptions", format_duration(task_stats.focus_secs), task_stats.sessions, task_stats.interruptions)}
+                        } else {
+                            {format!("{} · {} sessions", format_duration(task_stats.focus_secs), task_stats.sessions)}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SessionList() -> Element {
+    // None while not editing, Some(None) while adding a session
+    let mut editing = use_signal(|| None::<Option<SessionRecord>>);
+
+    let recent: Vec<SessionRecord> = HISTORY
+        .read()
+        .iter()
+        .rev()
+        .take(RECENT_SESSIONS)
+        .cloned()
+        .collect();
+
+    rsx! {
+        div {
+            class: "space-y-2",
+            // editing shouldn't close the overlay
+            onclick: move |e| e.stop_propagation(),
+            div {
+                class: "flex items-center justify-between",
+                h2 {
+                    class: "text-2xl font-bold",
+                    "Sessions"
+                }
+                button {
+                    class: "text-xl font-bold cursor-pointer",
+                    title: "Add a session the timer didn't record",
+                    onclick: move |_| editing.set(Some(None)),
+                    "Add"
+                }
+            }
+            if let Some(original) = editing() {
+                SessionEditor {
+                    // a fresh form for every session
+                    key: "{original.as_ref().map(|record| record.id).unwrap_or_default()}",
+                    original,
+                    onclose: move |_| editing.set(None),
+                }
+            }
+            if recent.is_empty() {
+                p { "No sessions yet" }
+            }
+            for record in recent {
+                SessionRow {
+                    key: "{record.id}",
+                    record: record.clone(),
+                    onedit: move |record| editing.set(Some(Some(record))),
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SessionRow(record: SessionRecord, onedit: EventHandler<SessionRecord>) -> Element {
+    let mut details = vec![record.kind.label().to_string()];
+    if !record.completed {
+        details.push("skipped".to_string());
+    }
+    if !record.interruptions.is_empty() {
+        details.push(format!("{} interrupted", record.interruptions.len()));
+    }
+    if let Some(task) = &record.task {
+        details.push(task.to_string());
+    }
+
+    let edited = record.clone();
+    let removed = record.clone();
+
+    rsx! {
+        div {
+            class: "flex items-center space-x-2 text-xl",
+            span {
+                class: "font-mono",
+                {format!("{} {}–{}", record.started_at.format("%a %d %b"), record.started_at.format("%H:%M"), record.ended_at.format("%H:%M"))}
+            }
+            span {
+                class: "flex-grow truncate",
+                {details.join(" · ")}
+            }
+            button {
+                class: "cursor-pointer",
+                title: "Edit session",
+                onclick: move |_| onedit.call(edited.clone()),
+                "Edit"
+            }
+            button {
+                class: "cursor-pointer",
+                title: "Delete session",
+                onclick: move |_| remove_record(&removed),
+                "✕"
+            }
+        }
+    }
+}
+
+/// Form for a new session, or for changing `original`
+#[component]
+fn SessionEditor(original: Option<SessionRecord>, onclose: EventHandler) -> Element {
+    // a new session defaults to a focus session that just ended
+    let (initial_kind, started_at, ended_at, initial_task) = match &original {
+        Some(record) => (
+            record.kind,
+            record.started_at,
+            record.ended_at,
+            record
+                .task
+                .as_ref()
+                .map(|task| task.to_string())
+                .unwrap_or_default(),
+        ),
+        None => {
+            let now = Local::now();
+            (
+                SessionKind::Focus,
+                now - TimeDelta::minutes(25),
+                now,
+                String::new(),
+            )
+        }
+    };
+
+    let mut kind = use_signal(|| initial_kind);
+    let mut date = use_signal(|| started_at.format("%Y-%m-%d").to_string());
+    let mut start = use_signal(|| started_at.format("%H:%M").to_string());
+    let mut end = use_signal(|| ended_at.format("%H:%M").to_string());
+    let mut task = use_signal(|| initial_task);
+    let mut error = use_signal(|| None::<String>);
+
+    let input_class = format!(
+        "rounded-lg px-2 py-1 text-xl {} {}",
+        *BG_COLOR_INVERTED.read(),
+        *TEXT_COLOR_INVERTED.read()
+    );
+
+    let save = move |_| {
+        let result = parse_manual_session(
+            *kind.peek(),
+            &date.peek(),
+            &start.peek(),
+            &end.peek(),
+            &task.peek(),
+        )
+        .and_then(|record| match &original {
+            Some(original) => edit_record(original, record),
+            None => add_manual_record(record),
+        });
+        match result {
+            Ok(()) => onclose.call(()),
+            Err(e) => error.set(Some(e)),
+        }
+    };
+
+    rsx! {
+        div {
+            class: "space-y-2",
+            div {
+                class: "flex items-center space-x-2",
+                select {
+                    class: input_class.clone(),
+                    title: "Kind",
+                    onchange: move |e: FormEvent| {
+                        if let Some(selected) = SessionKind::ALL.into_iter().find(|k| k.label() == e.value()) {
+                            kind.set(selected);
+                        }
+                    },
+                    for option in SessionKind::ALL {
+                        option {
+                            value: option.label(),
+                            selected: option == kind(),
+                            {option.label()}
+                        }
+                    }
+                }
+                input {
+                    r#type: "text",
+                    class: format!("w-32 {}", input_class),
+                    title: "Date",
+                    placeholder: "2025-06-02",
+                    value: "{date}",
+                    oninput: move |e: FormEvent| date.set(e.value()),
+                }
+                input {
+                    r#type: "text",
+                    class: format!("w-20 text-right {}", input_class),
+                    title: "Start",
+                    placeholder: "9:00",
+                    value: "{start}",
+                    oninput: move |e: FormEvent| start.set(e.value()),
+                }
+                input {
+                    r#type: "text",
+                    class: format!("w-20 text-right {}", input_class),
+                    title: "End",
+                    placeholder: "9:25",
+                    value: "{end}",
+                    oninput: move |e: FormEvent| end.set(e.value()),
+                }
+            }
+            if kind() == SessionKind::Focus {
+                input {
+                    r#type: "text",
+                    class: format!("w-full {}", input_class),
+                    title: "Task",
+                    placeholder: "Task @project #tag",
+                    value: "{task}",
+                    oninput: move |e: FormEvent| task.set(e.value()),
+                }
+            }
+            if let Some(error) = error.read().as_ref() {
+                p {
+                    class: "font-bold",
+                    "{error}"
+                }
+            }
+            div {
+                class: "flex justify-end space-x-4 text-xl font-bold",
+                button {
+                    class: "cursor-pointer",
+                    onclick: move |_| onclose.call(()),
+                    "Cancel"
+                }
+                button {
+                    class: "cursor-pointer",
+                    title: "Save session",
+                    onclick: save,
+                    "Save"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use dioxus::prelude::*;
+
+use crate::{
+    goals::goal_progress,
+    history::{TaskLabel, CURRENT_TASK},
+    settings::SETTINGS,
+    state::{BG_COLOR_INVERTED, PHASE_INDEX, TEXT_COLOR_INVERTED},
+    tasks::activate_task,
+    tray::set_tray_tooltip,
+};
+
+/// Keeps the tray tooltip in sync with the session type, the current task and the daily goal
+pub fn use_tray_tooltip() {
+    use_effect(|| {
+        let session_type = SETTINGS.read().phase(*PHASE_INDEX.read()).name;
+
+        let mut tooltip = match CURRENT_TASK.read().as_ref() {
+            Some(task) => format!("AuraTimer: {} – {}", session_type, task),
+            None => format!("AuraTimer: {}", session_type),
+        };
+        if let Some(progress) = goal_progress() {
+            tooltip.push_str(&format!(" ({} today)", progress.summary()));
+        }
+        set_tray_tooltip(&tooltip);
+    });
+}
+
+#[component]
+pub fn TaskLabelInput() -> Element {
+    let mut editing = use_signal(|| false);
+    let mut input = use_signal(String::new);
+
+    let bg_color = *BG_COLOR_INVERTED.read();
+    let text_color = *TEXT_COLOR_INVERTED.read();
+
+    let mut commit = move || {
+        // the input also loses focus after Enter or Escape
+        if !*editing.peek() {
+            return;
+        }
+        // a label typed by hand doesn't belong to any task in the task list
+        activate_task(None);
+        *CURRENT_TASK.write() = TaskLabel::parse(&input.peek());
+        editing.set(false);
+    };
+
+    if *editing.read() {
+        rsx! {
+            input {
+                r#type: "text",
+                class: format!("rounded-lg px-2 py-1 w-80 text-base font-normal text-center {} {}", bg_color, text_color),
+                placeholder: "Task @project #tag",
+                value: "{input}",
+                autofocus: true,
+                oninput: move |e: FormEvent| input.set(e.value()),
+                onkeydown: move |e: KeyboardEvent| {
+                    match e.key() {
+                        Key::Enter => commit(),
+                        Key::Escape => editing.set(false),
+                        _ => {}
+                    }
+                },
+                onblur: move |_| commit(),
+            }
+        }
+    } else {
+        let task = CURRENT_TASK.read().as_ref().map(ToString::to_string);
+        let opacity = if task.is_some() { "1" } else { "0.5" };
+
+        rsx! {
+            p {
+                class: "text-base font-normal cursor-pointer",
+                title: "Set the task for this focus session",
+                opacity: opacity,
+                onclick: move |_| {
+                    input.set(
+                        CURRENT_TASK
+                            .peek()
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_default(),
+                    );
+                    editing.set(true);
+                },
+                {task.unwrap_or("What are you working on?".to_string())}
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use dioxus::prelude::*;
+
+use crate::{
+    state::{BG_COLOR, BG_COLOR_INVERTED, ICON_COLOR, SHOW_TASKS, TEXT_COLOR_INVERTED},
+    task_source::TASK_SOURCE_ERROR,
+    tasks::{
+        activate_task, add_task, move_task, set_task_archived, set_task_done, set_task_estimate,
+        Task, TASK_LIST,
+    },
+    ui::icons::{Icon, IconType},
+};
+
+pub fn toggle_task_list() {
+    let show_tasks = *SHOW_TASKS.peek();
+    *SHOW_TASKS.write() = !show_tasks;
+}
+
+#[component]
+pub fn TaskListPanel() -> Element {
+    let mut new_task = use_signal(String::new);
+    let mut new_estimate = use_signal(|| 1u32);
+    let mut show_archived = use_signal(|| false);
+
+    let bg_color = *BG_COLOR.read();
+    let input_bg_color = *BG_COLOR_INVERTED.read();
+    let input_text_color = *TEXT_COLOR_INVERTED.read();
+
+    let task_list = TASK_LIST.read().clone();
+    let (archived, tasks): (Vec<Task>, Vec<Task>) =
+        task_list.tasks.into_iter().partition(|task| task.archived);
+
+    let mut submit = move || {
+        add_task(&new_task.peek(), *new_estimate.peek());
+        new_task.set(String::new());
+        new_estimate.set(1);
+    };
+
+    rsx! {
+        div {
+            class: format!("absolute top-0 left-0 w-full h-full z-20 pt-12 px-8 pb-8 overflow-y-auto flex flex-col items-center {}", bg_color),
+            div {
+                class: "w-full max-w-lg space-y-6",
+                div {
+                    class: "flex items-center justify-between",
+                    h1 {
+                        class: "text-4xl font-bold",
+                        "Tasks"
+                    }
+                    button {
+                        class: "text-xl font-bold cursor-pointer",
+                        title: "Close task list",
+                        onclick: move |_| *SHOW_TASKS.write() = false,
+                        "Done"
+                    }
+                }
+                div {
+                    class: "flex items-center space-x-2",
+                    input {
+                        r#type: "text",
+                        class: format!("flex-grow rounded-lg px-2 py-1 text-xl {} {}", input_bg_color, input_text_color),
+                        placeholder: "New task @project #tag",
+                        value: "{new_task}",
+                        oninput: move |e: FormEvent| new_task.set(e.value()),
+                        onkeydown: move |e: KeyboardEvent| {
+                            if e.key() == Key::Enter {
+                                submit();
+                            }
+                        },
+                    }
+                    input {
+                        r#type: "number",
+                        class: format!("rounded-lg px-2 py-1 w-16 text-xl text-right {} {}", input_bg_color, input_text_color),
+                        title: "Estimated pomodoros",
+                        min: "1",
+                        max: "20",
+                        value: "{new_estimate}",
+                        oninput: move |e: FormEvent| {
+                            if let Ok(estimate) = e.value().trim().parse::<u32>() {
+                                new_estimate.set(estimate.clamp(1, 20));
+                            }
+                        },
+                    }
+                    button {
+                        class: "text-xl font-bold cursor-pointer",
+                        title: "Add task",
+                        onclick: move |_| submit(),
+                        "Add"
+                    }
+                }
+                if let Some(error) = TASK_SOURCE_ERROR.read().as_ref() {
+                    p {
+                        class: "font-bold",
+                        "{error}"
+                    }
+                }
+                if tasks.is_empty() {
+                    p { "No tasks yet" }
+                }
+                for task in tasks {
+                    TaskRow { key: "{task.id}", task: task.clone(), active: task_list.active_task == Some(task.id) }
+                }
+                if !archived.is_empty() {
+                    button {
+                        class: "text-xl font-bold cursor-pointer",
+                        onclick: move |_| show_archived.toggle(),
+                        if *show_archived.read() {
+                            "Hide archived ({archived.len()})"
+                        } else {
+                            "Show archived ({archived.len()})"
+                        }
+                    }
+                    if *show_archived.read() {
+                        for task in archived {
+                            TaskRow { key: "{task.id}", task: task.clone(), active: false }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TaskRow(task: Task, active: bool) -> Element {
+    let icon_color = *ICON_COLOR.read();
+    let id = task.id;
+    let done = task.done;
+    let archived = task.archived;
+    let estimate = task.estimated_pomodoros;
+
+    rsx! {
+        div {
+            class: "flex items-center space-x-2 text-xl",
+            opacity: if done || archived { "0.5" } else { "1" },
+            input {
+                r#type: "checkbox",
+                class: "w-6 h-6",
+                title: "Mark as done",
+                checked: done,
+                onchange: move |e: FormEvent| set_task_done(id, e.checked()),
+            }
+            span {
+                class: if active { "flex-grow font-bold cursor-pointer" } else { "flex-grow cursor-pointer" },
+                title: match (active, task.imported) {
+                    (true, _) => "Active task, click to deactivate",
+                    (false, true) => "Imported from the task file, click to make it the active task",
+                    (false, false) => "Make this the active task",
+                },
+                onclick: move |_| {
+                    if !archived && !done {
+                        activate_task(if active { None } else { Some(id) });
+                    }
+                },
+                if done {
+                    s { {task.name.clone()} }
+                } else {
+                    {task.name.clone()}
+                }
+            }
+            PomodoroDots { estimated: estimate, actual: task.actual_pomodoros, class: icon_color }
+            if !archived {
+                button {
+                    class: "cursor-pointer",
+                    title: "Fewer estimated pomodoros",
+                    onclick: move |_| set_task_estimate(id, estimate.saturating_sub(1).max(1)),
+                    "−"
+                }
+                button {
+                    class: "cursor-pointer",
+                    title: "More estimated pomodoros",
+                    onclick: move |_| set_task_estimate(id, (estimate + 1).min(20)),
+                    "+"
+                }
+                button {
+                    class: "cursor-pointer",
+                    title: "Move up",
+                    onclick: move |_| move_task(id, -1),
+                    "↑"
+                }
+                button {
+                    class: "cursor-pointer",
+                    title: "Move down",
+                    onclick: move |_| move_task(id, 1),
+                    "↓"
+                }
+            }
+            // imported tasks are removed by deleting them from the file instead
+            if !task.imported {
+                button {
+                    class: "cursor-pointer",
+                    title: if archived { "Restore" } else { "Archive" },
+                    onclick: move |_| set_task_archived(id, !archived),
+                    if archived { "Restore" } else { "Archive" }
+                }
+            }
+        }
+    }
+}
+
+/// Filled dots for finished pomodoros, outlined ones for the rest of the estimate
+#[component]
+fn PomodoroDots(estimated: u32, actual: u32, class: String) -> Element {
+    rsx! {
+        div {
+            class: "flex space-x-1 items-center",
+            title: "{actual} of {estimated} pomodoros",
+            for i in 0..estimated.max(actual) {
+                Icon {
+                    icon_type: if i < actual {
+                        IconType::CircleFilled
+                    } else {
+                        IconType::CircleOutlined
+                    },
+                    size: "1rem",
+                    opacity: if i < estimated { 1.0 } else { 0.5 },
+                    class: class.clone(),
+                }
+            }
+        }
+    }
+}
@@ -1,95 +1,434 @@
 use std::time::Duration;
 
-use core_graphics::display::{CGDisplayBounds, CGGetActiveDisplayList, CGPoint};
+use chrono::{DateTime, Local};
 use dioxus::{
-    desktop::{window, Config, LogicalPosition, WindowBuilder},
+    desktop::{
+        tao::{dpi::PhysicalPosition, monitor::MonitorHandle},
+        window, Config, LogicalSize, WindowBuilder,
+    },
     prelude::*,
 };
 use tokio::time::Instant;
 use tracing::error;
-use tray_icon::dpi::LogicalSize;
 
 use crate::{
-    components::alert::Alert,
+    bus::{emit_event, SessionEvent, SessionEventKind, TimerCommand},
+    components::{alert::Alert, timer_expired::dismiss_expiry},
+    cycle::Cycle,
+    history::{
+        add_record, record_session, remove_record, SessionKind, SessionOutcome, SessionRecord,
+        CURRENT_TASK,
+    },
+    interruptions::{log_interruption, open_interruption_prompt, Interruption, INTERRUPTIONS},
+    settings::{AlertDisplay, AlertPosition, Phase, Settings, SETTINGS},
     sound::play_alarm,
-    state::{
-        BG_COLOR_HOVER, FULL_SESSION_COUNT, ICON_COLOR, IS_FOCUS_MODE, SMALL_SESSION_COUNT,
-        TIMER_EXPIRED,
+    state::{BG_COLOR_HOVER, ICON_COLOR, PHASE_INDEX, TIMER_EXPIRED},
+    tasks::complete_pomodoro,
+    timer_model::{
+        Progress, TimerAction, TimerEffects, TimerModel, UndoAction, UndoEntry, UNDO_LIMIT,
     },
     tray::set_tray_title,
     ui::icons::{Icon, IconType},
+    undo::UndoStack,
 };
 
-const FOCUS_DURATION: u32 = 25 * 60 * 1000;
-const SHORT_BREAK_DURATION: u32 = 5 * 60 * 1000;
-const LONG_BREAK_DURATION: u32 = 20 * 60 * 1000;
-
-static TIMER_RUNNING: GlobalSignal<bool> = GlobalSignal::new(|| false);
-static MILLIS_REMAINING: GlobalSignal<u32> = GlobalSignal::new(|| FOCUS_DURATION);
-
-static LAST_SAVED_TIME: GlobalSignal<u32> = GlobalSignal::new(|| 0);
-pub static SKIPPED_SESSION: GlobalSignal<bool> = GlobalSignal::new(|| false);
+pub static TIMER_RUNNING: GlobalSignal<bool> = GlobalSignal::new(|| false);
+pub static MILLIS_REMAINING: GlobalSignal<u32> =
+    GlobalSignal::new(|| SETTINGS.peek().session_duration(0));
+// remaining time when the timer was last (re)started, the countdown is calculated from this,
+// or the time already counted up in flowtime
+static MILLIS_AT_START: GlobalSignal<u32> = GlobalSignal::new(|| 0);
+/// Time counted up in a flowtime focus session
+pub static MILLIS_ELAPSED: GlobalSignal<u32> = GlobalSignal::new(|| 0);
+// length of the break earned in the last flowtime focus session, used instead of the phase duration
+static FLOW_BREAK: GlobalSignal<Option<u32>> = GlobalSignal::new(|| None);
 
 static START_TIME: GlobalSignal<Option<Instant>> = GlobalSignal::new(|| None);
+// wall clock time the current session was first started, for the session history
+static SESSION_STARTED_AT: GlobalSignal<Option<DateTime<Local>>> = GlobalSignal::new(|| None);
+// bumped for every countdown loop, so a stale loop stops even if the timer was restarted before it noticed
+static COUNTDOWN_RUN: GlobalSignal<u64> = GlobalSignal::new(|| 0);
+
+const SNOOZE: Duration = Duration::from_secs(5 * 60);
+// when a snoozed expiry comes back
+static SNOOZED_UNTIL: GlobalSignal<Option<Instant>> = GlobalSignal::new(|| None);
+// the focus session waiting for the screen to be unlocked
+static LOCKED_FOCUS: GlobalSignal<Option<Box<Progress>>> = GlobalSignal::new(|| None);
+
+// actions that can be undone or redone, kept for the `TimerModel` of the next action
+static UNDO: GlobalSignal<UndoStack<UndoEntry>> = GlobalSignal::new(|| UndoStack::new(UNDO_LIMIT));
+
+/// The timer as shown by the GlobalSignals
+fn current_progress() -> Progress {
+    Progress {
+        cycle: Cycle::current(),
+        millis_remaining: *MILLIS_REMAINING.peek(),
+        millis_elapsed: *MILLIS_ELAPSED.peek(),
+        flow_break: *FLOW_BREAK.peek(),
+        running: *TIMER_RUNNING.peek(),
+        expired: *TIMER_EXPIRED.peek(),
+        session_started_at: *SESSION_STARTED_AT.peek(),
+        snoozed_until: *SNOOZED_UNTIL.peek(),
+        interruptions: INTERRUPTIONS.peek().clone(),
+        locked_focus: LOCKED_FOCUS.peek().clone(),
+    }
+}
 
-pub fn clear_timer() {
-    let small_session_count = *SMALL_SESSION_COUNT.peek();
+fn set<T: PartialEq + 'static>(signal: &'static GlobalSignal<T>, value: T) {
+    if *signal.peek() != value {
+        *signal.write() = value;
+    }
+}
 
-    *TIMER_RUNNING.write() = false;
-    *MILLIS_REMAINING.write() = if *IS_FOCUS_MODE.peek() {
-        FOCUS_DURATION
-    } else if small_session_count % 4 == 0 && small_session_count != 0 {
-        LONG_BREAK_DURATION
+/// Shows `progress` through the GlobalSignals
+fn show_progress(progress: Progress) {
+    if Cycle::current() != progress.cycle {
+        progress.cycle.apply();
+    }
+    set(&MILLIS_REMAINING, progress.millis_remaining);
+    set(&MILLIS_ELAPSED, progress.millis_elapsed);
+    set(&FLOW_BREAK, progress.flow_break);
+    set(&TIMER_EXPIRED, progress.expired);
+    set(&SESSION_STARTED_AT, progress.session_started_at);
+    set(&SNOOZED_UNTIL, progress.snoozed_until);
+    set(&INTERRUPTIONS, progress.interruptions);
+    set(&LOCKED_FOCUS, progress.locked_focus);
+
+    // a running timer continues from where it is now, with a new countdown loop since the last one may have stopped
+    if progress.running {
+        *START_TIME.write() = Some(Instant::now());
+        *MILLIS_AT_START.write() = countdown_start();
+        *TIMER_RUNNING.write() = true;
     } else {
-        SHORT_BREAK_DURATION
+        set(&TIMER_RUNNING, false);
+    }
+}
+
+/// Carries out the history, events and alerts of the timer's actions
+struct AppEffects;
+
+impl TimerEffects for AppEffects {
+    fn emit(&mut self, kind: SessionEventKind, progress: &Progress) {
+        emit(kind, progress);
+    }
+
+    fn record(
+        &mut self,
+        kind: SessionKind,
+        started_at: DateTime<Local>,
+        duration_secs: u32,
+        outcome: SessionOutcome,
+        interruptions: Vec<Interruption>,
+    ) -> SessionRecord {
+        let record = record_session(kind, started_at, duration_secs, outcome, interruptions);
+        if outcome == SessionOutcome::Completed && kind == SessionKind::Focus {
+            complete_pomodoro();
+        }
+        record
+    }
+
+    fn restore_record(&mut self, record: SessionRecord) -> SessionRecord {
+        add_record(record)
+    }
+
+    fn remove_record(&mut self, record: &SessionRecord) {
+        remove_record(record);
+    }
+
+    fn ring(&mut self, alert: bool) {
+        play_alarm();
+        if alert && SETTINGS.peek().show_alert_window {
+            show_alert_window(|| VirtualDom::new(Alert));
+        }
+    }
+}
+
+/// Applies `action` to the timer as shown and shows the result
+fn dispatch(action: TimerAction) {
+    let settings = SETTINGS.peek().clone();
+    let mut model = TimerModel {
+        progress: current_progress(),
+        undo: UNDO.peek().clone(),
     };
-    *TIMER_EXPIRED.write() = false;
+    let before = model.progress.clone();
+    model.apply(action, &settings, Local::now(), &mut AppEffects);
+
+    let expired = model.progress.expired;
+    let snoozed = model.progress.snoozed_until.is_some();
+    if model.progress != before {
+        show_progress(model.progress);
+    }
+    if *UNDO.peek() != model.undo {
+        *UNDO.write() = model.undo;
+    }
+
+    if expired {
+        set_tray_title(&format!("{} time!", current_phase().name));
+    } else if snoozed {
+        set_tray_title("Snoozed");
+    } else {
+        update_tray_countdown();
+    }
 }
 
 pub fn start_timer() {
-    clear_timer();
+    dispatch(TimerAction::Start);
+}
 
-    *START_TIME.write() = Some(Instant::now());
-    *TIMER_RUNNING.write() = true;
+fn current_phase() -> Phase {
+    SETTINGS.peek().phase(*PHASE_INDEX.peek())
 }
 
-pub fn next_session() {
-    let is_focus_mode = *IS_FOCUS_MODE.peek();
-    *IS_FOCUS_MODE.write() = !is_focus_mode;
+/// Whether the timer counts up because the focus session runs in flowtime
+pub fn counting_up() -> bool {
+    SETTINGS.peek().flowtime && current_phase().is_focus()
+}
 
-    *LAST_SAVED_TIME.write() = *MILLIS_REMAINING.read();
-    *SKIPPED_SESSION.write() = true;
+fn countdown_start() -> u32 {
+    if counting_up() {
+        *MILLIS_ELAPSED.peek()
+    } else {
+        *MILLIS_REMAINING.peek()
+    }
+}
 
-    let small_session_count = *SMALL_SESSION_COUNT.peek();
-    // used remainder for session stats
-    if small_session_count % 4 == 0 && small_session_count != 0 {
-        *FULL_SESSION_COUNT.write() += 1;
+fn emit(kind: SessionEventKind, progress: &Progress) {
+    let settings = SETTINGS.peek();
+    let phase = progress.phase(&settings);
+    let sessions = progress.cycle.sessions;
+
+    emit_event(SessionEvent {
+        kind,
+        at: Local::now(),
+        phase: phase.kind,
+        phase_name: phase.name.clone(),
+        planned_secs: progress.phase_duration(&settings) / 1000,
+        remaining_secs: progress.millis_remaining / 1000,
+        task: if phase.is_focus() {
+            CURRENT_TASK.peek().clone()
+        } else {
+            None
+        },
+        session_number: if phase.is_focus() {
+            sessions + 1
+        } else {
+            sessions.max(1)
+        },
+    });
+}
+
+/// Adjusts the current session to changed durations, keeping the time already spent in it
+pub fn apply_settings(previous: &Settings) {
+    if *TIMER_EXPIRED.peek() {
+        return;
+    }
+
+    if previous.flowtime != SETTINGS.peek().flowtime && current_phase().is_focus() {
+        switch_counting_direction();
+        return;
+    }
+    // the running session doesn't follow the configured durations
+    if counting_up() || (FLOW_BREAK.peek().is_some() && !current_phase().is_focus()) {
+        return;
     }
-    if !is_focus_mode {
-        *SMALL_SESSION_COUNT.write() += 1;
+
+    let index = *PHASE_INDEX.peek();
+    let old_duration = previous.session_duration(index);
+    let new_duration = SETTINGS.peek().session_duration(index);
+    if old_duration == new_duration {
+        return;
     }
 
-    clear_timer();
+    let elapsed = old_duration.saturating_sub(*MILLIS_REMAINING.peek());
+    // if the session is already longer than the new duration, let it expire right away
+    let remaining = new_duration.saturating_sub(elapsed).max(1000);
 
-    let minutes = *MILLIS_REMAINING.peek() / 1000 / 60;
-    let seconds = *MILLIS_REMAINING.peek() / 1000 % 60;
-    update_tray(minutes, seconds);
+    if *TIMER_RUNNING.peek() {
+        *START_TIME.write() = Some(Instant::now());
+        *MILLIS_AT_START.write() = remaining;
+    }
+    *MILLIS_REMAINING.write() = remaining;
+}
+
+/// Keeps the time spent in the focus session when flowtime is turned on or off during it
+fn switch_counting_direction() {
+    let duration = current_phase().duration();
+    if counting_up() {
+        *MILLIS_ELAPSED.write() = duration.saturating_sub(*MILLIS_REMAINING.peek());
+    } else {
+        *MILLIS_REMAINING.write() = duration.saturating_sub(*MILLIS_ELAPSED.peek()).max(1000);
+        *MILLIS_ELAPSED.write() = 0;
+    }
+
+    if *TIMER_RUNNING.peek() {
+        *START_TIME.write() = Some(Instant::now());
+        *MILLIS_AT_START.write() = countdown_start();
+    }
+}
+
+pub fn toggle_timer() {
+    dispatch(TimerAction::Toggle);
+}
+
+/// Pauses the running session, also a strict break, e.g. one that only lasts while the screen is locked
+#[cfg(target_os = "linux")]
+pub fn pause_timer() {
+    dispatch(TimerAction::Pause { idle_millis: 0 });
+}
+
+/// Pauses the running focus session because the user walked away, optionally giving back the time they were away
+pub fn pause_for_idle(idle: Duration) {
+    if !*TIMER_RUNNING.peek() || !current_phase().is_focus() {
+        return;
+    }
+
+    let idle_millis = if SETTINGS.peek().subtract_idle_time {
+        idle.as_millis().try_into().unwrap_or(u32::MAX)
+    } else {
+        0
+    };
+    dispatch(TimerAction::Pause { idle_millis });
+}
+
+/// Whether a running break can't be paused, restarted or skipped because strict breaks are on,
+/// reruns the calling effect when it changes. A break while the screen is locked ends with the unlock anyway.
+pub fn read_break_enforced() -> bool {
+    let settings = SETTINGS.read();
+    settings.strict_breaks
+        && !settings.phase(*PHASE_INDEX.read()).is_focus()
+        && *TIMER_RUNNING.read()
+        && LOCKED_FOCUS.read().is_none()
+}
+
+pub fn next_session() {
+    dispatch(TimerAction::Skip);
+}
+
+/// Whether a session was started and not finished yet, running or paused
+pub fn session_in_progress() -> bool {
+    SESSION_STARTED_AT.peek().is_some()
+}
+
+/// Starts the sequence over at its first focus phase, e.g. when working hours begin,
+/// a paused session is resumed instead
+pub fn start_first_focus() {
+    dispatch(TimerAction::StartFirstFocus);
+}
+
+/// Starts the sequence and its counts over for a new day, see `Settings::reset_count_daily`
+pub fn start_new_day() {
+    dispatch(TimerAction::NewDay);
 }
 
-pub fn revert_session() {
-    let is_focus_mode = *IS_FOCUS_MODE.peek();
+/// Pauses the running focus session and runs the break after it until `end_lock_break`,
+/// e.g. when the screen gets locked. Returns whether a break was started.
+#[cfg(target_os = "linux")]
+pub fn take_break_now() -> bool {
+    dispatch(TimerAction::LockBreak);
+    LOCKED_FOCUS.peek().is_some()
+}
+
+/// Brings back the focus session paused by `take_break_now` with the time it had left,
+/// returns whether there was one
+#[cfg(target_os = "linux")]
+pub fn end_lock_break() -> bool {
+    let locked = LOCKED_FOCUS.peek().is_some();
+    dispatch(TimerAction::Unlock);
+    locked
+}
+
+/// Ends a strict break early, the skip is logged in the history
+pub fn emergency_skip_break() {
+    dispatch(TimerAction::EmergencySkip);
+}
 
-    if is_focus_mode {
-        *SMALL_SESSION_COUNT.write() -= 1;
+pub fn handle_timer_command(command: TimerCommand) {
+    match command {
+        TimerCommand::Start => {
+            start_timer();
+        }
+        TimerCommand::Dismiss => {
+            if *TIMER_EXPIRED.peek() {
+                dismiss_expiry();
+            }
+        }
+        TimerCommand::Toggle => {
+            if *TIMER_EXPIRED.peek() {
+                dismiss_expiry();
+                start_timer();
+            } else {
+                toggle_timer();
+            }
+        }
+        TimerCommand::Skip => {
+            if !*TIMER_EXPIRED.peek() {
+                next_session();
+            }
+        }
+        TimerCommand::EmergencySkip => {
+            emergency_skip_break();
+        }
+        TimerCommand::Snooze => snooze(),
+        TimerCommand::Interrupt(Some(kind)) => log_interruption(kind, ""),
+        TimerCommand::Interrupt(None) => open_interruption_prompt(),
+        TimerCommand::Undo => undo(),
+        TimerCommand::Redo => redo(),
     }
+}
 
-    *IS_FOCUS_MODE.write() = !is_focus_mode;
+/// Resets the countdown of the current session, can be undone
+pub fn restart_timer() {
+    dispatch(TimerAction::Restart);
+}
+
+/// Goes back to the stopped timer of the next session after an expiry
+pub fn dismiss() {
+    dispatch(TimerAction::Dismiss);
+}
+
+/// Brings the expiry back after a few minutes instead of moving on right away
+pub fn snooze() {
+    let until = Instant::now() + SNOOZE;
+    dispatch(TimerAction::Snooze(until));
+    if *SNOOZED_UNTIL.peek() != Some(until) {
+        return;
+    }
+
+    spawn(async move {
+        tokio::time::sleep_until(until).await;
+        dispatch(TimerAction::SnoozeOver(until));
+    });
+}
+
+/// The action `undo` would take back, e.g. for button titles
+pub fn undo_action() -> Option<UndoAction> {
+    UNDO.read().last().map(|entry| entry.action)
+}
 
-    *TIMER_RUNNING.write() = false;
-    *MILLIS_REMAINING.write() = *LAST_SAVED_TIME.peek();
+pub fn redo_action() -> Option<UndoAction> {
+    UNDO.read().next().map(|entry| entry.action)
+}
 
-    *SKIPPED_SESSION.write() = false;
+/// Takes back the most recent skip, restart, start or snooze, several in a row if needed
+pub fn undo() {
+    dispatch(TimerAction::Undo);
+}
+
+/// Does the most recently undone action again
+pub fn redo() {
+    dispatch(TimerAction::Redo);
+}
+
+/// Undoes a skip right before, restarts the timer otherwise
+pub fn restart_or_revert() {
+    if UNDO.peek().last().map(|entry| entry.action) == Some(UndoAction::Skip) {
+        undo();
+    } else {
+        restart_timer();
+    }
+}
 
+fn update_tray_countdown() {
     let minutes = *MILLIS_REMAINING.peek() / 1000 / 60;
     let seconds = *MILLIS_REMAINING.peek() / 1000 % 60;
     update_tray(minutes, seconds);
@@ -104,8 +443,15 @@ pub fn Timer() -> Element {
 
     use_effect(move || {
         formatted_time.set({
-            let minutes = *MILLIS_REMAINING.read() / 1000 / 60;
-            let seconds = *MILLIS_REMAINING.read() / 1000 % 60;
+            let settings = SETTINGS.read();
+            let millis = if settings.flowtime && settings.phase(*PHASE_INDEX.read()).is_focus() {
+                *MILLIS_ELAPSED.read()
+            } else {
+                *MILLIS_REMAINING.read()
+            };
+            drop(settings);
+            let minutes = millis / 1000 / 60;
+            let seconds = millis / 1000 % 60;
 
             // Only update tray title if seconds actually changed for performance
             if *last_seconds.peek() != Some(seconds) {
@@ -117,17 +463,6 @@ pub fn Timer() -> Element {
         });
     });
 
-    let toggle_timer = move |_| {
-        if !*TIMER_RUNNING.read() {
-            *START_TIME.write() = Some(Instant::now());
-            *TIMER_RUNNING.write() = true;
-
-            *SKIPPED_SESSION.write() = false;
-        } else {
-            *TIMER_RUNNING.write() = false;
-        }
-    };
-
     use_effect(move || {
         if *TIMER_RUNNING.read() {
             if let Some(timer_start) = *START_TIME.peek() {
@@ -136,43 +471,33 @@ pub fn Timer() -> Element {
                     let mut interval = tokio::time::interval(interval);
 
                     let remaining_time = *MILLIS_REMAINING.peek();
+                    *COUNTDOWN_RUN.write() += 1;
+                    let run = *COUNTDOWN_RUN.peek();
 
                     if remaining_time > 0 {
                         loop {
                             interval.tick().await;
-                            if !*TIMER_RUNNING.peek() {
+                            // finishing a flowtime session or undoing can start a new loop before this one sees the pause
+                            if !*TIMER_RUNNING.peek() || *COUNTDOWN_RUN.peek() != run {
                                 break;
                             }
+                            // re-read every tick since changing the settings can shift the countdown
+                            let timer_start = START_TIME.peek().unwrap_or(timer_start);
                             let elapsed_time = timer_start.elapsed();
-                            let remaining_time = Duration::from_millis(remaining_time as u64)
-                                .saturating_sub(elapsed_time)
-                                .as_millis();
+                            if counting_up() {
+                                let elapsed = Duration::from_millis(*MILLIS_AT_START.peek() as u64)
+                                    + elapsed_time;
+                                *MILLIS_ELAPSED.write() =
+                                    elapsed.as_millis().try_into().unwrap_or(u32::MAX);
+                                continue;
+                            }
+                            let remaining_time =
+                                Duration::from_millis(*MILLIS_AT_START.peek() as u64)
+                                    .saturating_sub(elapsed_time)
+                                    .as_millis();
                             *MILLIS_REMAINING.write() = remaining_time as u32;
                             if remaining_time == 0 {
-                                *TIMER_EXPIRED.write() = true;
-                                *TIMER_RUNNING.write() = false;
-
-                                let is_focus_mode = *IS_FOCUS_MODE.peek();
-
-                                let small_session_count = *SMALL_SESSION_COUNT.peek();
-                                // used remainder for session stats
-                                if small_session_count % 4 == 0 && small_session_count != 0 {
-                                    *FULL_SESSION_COUNT.write() += 1;
-                                }
-
-                                if !is_focus_mode {
-                                    *SMALL_SESSION_COUNT.write() += 1;
-
-                                    set_tray_title("Focus time!");
-                                } else {
-                                    set_tray_title("Break time!");
-                                }
-
-                                *IS_FOCUS_MODE.write() = !is_focus_mode;
-
-                                show_alert_window();
-
-                                play_alarm();
+                                dispatch(TimerAction::Expire);
                                 break;
                             }
                         }
@@ -193,7 +518,7 @@ pub fn Timer() -> Element {
             div {
                 class: format!("transition duration-200 absolute top-0 left-0 w-full h-full opacity-10 rounded-lg bg-transparent z-10 cursor-pointer flex items-center justify-center {}", color),
                 title: "Toggle timer",
-                onclick: toggle_timer,
+                onclick: move |_| toggle_timer(),
                 onmouseenter: move |_| hovering.set(true),
                 onmouseleave: move |_| hovering.set(false),
             }
@@ -213,81 +538,90 @@ pub fn Timer() -> Element {
 }
 
 fn update_tray(minutes: u32, seconds: u32) {
-    let session_type = if *IS_FOCUS_MODE.read() {
-        "Focus"
+    let session_type = SETTINGS.read().phase(*PHASE_INDEX.read()).name;
+    if SETTINGS.peek().show_tray_countdown {
+        set_tray_title(format!("{}: {:02}:{:02}", session_type, minutes, seconds).as_str());
     } else {
-        "Break"
-    };
-    set_tray_title(format!("{}: {:02}:{:02}", session_type, minutes, seconds).as_str());
+        set_tray_title(&session_type);
+    }
 }
 
-fn show_alert_window() {
-    let dom = VirtualDom::new(Alert);
-
-    let width = 600.0;
-    let height = 64.0;
-
-    let (active_display_id, display_width, _) = get_active_display_and_size();
-
-    let display_bounds = unsafe { CGDisplayBounds(active_display_id as u32) };
-
-    let x_pos = display_bounds.origin.x + (display_width / 2.0 - width / 2.0);
-    let y_pos = 64.0;
-
-    let config = Config::new()
-        .with_window(
-            WindowBuilder::new()
-                .with_inner_size(LogicalSize::new(width, height))
-                .with_position(LogicalPosition::new(x_pos, y_pos))
-                .with_closable(false)
-                .with_always_on_top(true)
-                .with_transparent(true)
-                .with_resizable(false)
-                .with_decorations(false),
-        )
-        .with_disable_context_menu(true);
-    window().new_window(dom, config);
-}
+fn alert_monitors() -> Vec<MonitorHandle> {
+    let handle = window();
+
+    let monitors: Vec<MonitorHandle> = match SETTINGS.peek().alert_display {
+        AlertDisplay::All => handle.available_monitors().collect(),
+        AlertDisplay::Primary => handle.primary_monitor().into_iter().collect(),
+        AlertDisplay::Cursor => handle
+            .cursor_position()
+            .ok()
+            .and_then(|cursor| handle.monitor_from_point(cursor.x, cursor.y))
+            .into_iter()
+            .collect(),
+    };
+    if !monitors.is_empty() {
+        return monitors;
+    }
 
-extern "C" {
-    fn CGEventCreate(source: *const std::ffi::c_void) -> *mut std::ffi::c_void;
-    fn CGEventGetLocation(event: *mut std::ffi::c_void) -> CGPoint;
-    fn CFRelease(cf: *mut std::ffi::c_void);
+    // e.g. the cursor position isn't available, so fall back to the primary display or any display at all
+    handle
+        .primary_monitor()
+        .or_else(|| handle.current_monitor())
+        .or_else(|| handle.available_monitors().next())
+        .into_iter()
+        .collect()
 }
 
-fn get_active_display_and_size() -> (i32, f64, f64) {
-    let mouse_location = unsafe {
-        let event = CGEventCreate(std::ptr::null());
-        let loc = CGEventGetLocation(event);
-        CFRelease(event);
-        loc
-    };
+/// Opens an alert window on the configured displays, `new_dom` creates the contents of each
+pub fn show_alert_window(new_dom: impl Fn() -> VirtualDom) {
+    let width = 600.0;
+    let height = 64.0;
+    let margin = 64.0;
 
-    // Get all active displays
-    let mut display_ids = [0u32; 16];
-    let mut display_count = 0;
-    unsafe {
-        CGGetActiveDisplayList(
-            display_ids.len() as u32,
-            display_ids.as_mut_ptr(),
-            &mut display_count,
-        );
+    let monitors = alert_monitors();
+    if monitors.is_empty() {
+        error!("No display found, letting the window manager place the alert");
     }
+    let positions: Vec<Option<PhysicalPosition<f64>>> = if monitors.is_empty() {
+        vec![None]
+    } else {
+        monitors
+            .iter()
+            .map(|monitor| {
+                let scale = monitor.scale_factor();
+                let origin = monitor.position();
+                let size = monitor.size();
+
+                let x = origin.x as f64 + (size.width as f64 - width * scale) / 2.0;
+                let y = match SETTINGS.peek().alert_position {
+                    AlertPosition::Top => origin.y as f64 + margin * scale,
+                    AlertPosition::Center => {
+                        origin.y as f64 + (size.height as f64 - height * scale) / 2.0
+                    }
+                    AlertPosition::Bottom => {
+                        origin.y as f64 + size.height as f64 - (margin + height) * scale
+                    }
+                };
+                Some(PhysicalPosition::new(x, y))
+            })
+            .collect()
+    };
 
-    for &display_id in &display_ids[..display_count as usize] {
-        let bounds = unsafe { CGDisplayBounds(display_id) };
-        if point_in_rect(mouse_location.x, mouse_location.y, &bounds) {
-            return (display_id as i32, bounds.size.width, bounds.size.height);
+    for position in positions {
+        let mut builder = WindowBuilder::new()
+            .with_inner_size(LogicalSize::new(width, height))
+            .with_closable(false)
+            .with_always_on_top(true)
+            .with_transparent(true)
+            .with_resizable(false)
+            .with_decorations(false);
+        if let Some(position) = position {
+            builder = builder.with_position(position);
         }
-    }
-    error!("Cursor is not on any detected display");
-
-    (1, 0.0, 0.0)
-}
 
-fn point_in_rect(x: f64, y: f64, rect: &core_graphics::geometry::CGRect) -> bool {
-    x >= rect.origin.x
-        && x < rect.origin.x + rect.size.width
-        && y >= rect.origin.y
-        && y < rect.origin.y + rect.size.height
+        let config = Config::new()
+            .with_window(builder)
+            .with_disable_context_menu(true);
+        window().new_window(new_dom(), config);
+    }
 }
@@ -1,43 +1,57 @@
-use dioxus::{desktop::window, prelude::*};
+use dioxus::prelude::*;
 
 use crate::{
-    components::timer::start_timer,
-    state::{ALERT_WINDOW_ID, IS_FOCUS_MODE, SMALL_SESSION_COUNT},
+    components::timer::{dismiss, snooze, start_timer},
+    settings::{Phase, SETTINGS},
+    state::PHASE_INDEX,
     ui::button::Button,
 };
 
+/// Goes back to the (stopped) timer of the next session without starting it, alert windows close themselves
+pub fn dismiss_expiry() {
+    dismiss();
+}
+
+/// Announces the phase that is up next
+pub fn expiry_title(phase: &Phase) -> String {
+    if phase.is_focus() {
+        "It's time to focus!".to_string()
+    } else {
+        format!("Time for a {}!", phase.name.to_lowercase())
+    }
+}
+
+pub fn start_label(phase: &Phase) -> &'static str {
+    if phase.is_focus() {
+        "Start focus"
+    } else {
+        "Start break"
+    }
+}
+
 #[component]
 pub fn TimerExpired() -> Element {
-    let is_focus_mode = *IS_FOCUS_MODE.read();
-    let small_session_count = *SMALL_SESSION_COUNT.read();
+    let phase = SETTINGS.read().phase(*PHASE_INDEX.read());
 
     rsx! {
         div {
             class: "text-6xl font-bold flex flex-col items-center justify-center text-center space-y-8",
             h1 {
-                if is_focus_mode {
-                    "It's time to focus!"
-                } else {
-                    if small_session_count % 4 == 0 && small_session_count != 0 {
-                        "It's time for your long break!"
-                    } else {
-                        "It's time for your short break!"
-                    }
-                }
+                {expiry_title(&phase)}
             }
-            Button {
-                title: "Start timer",
-                action: move || {
-                    if let Some(id) = *ALERT_WINDOW_ID.lock().unwrap() {
-                        window().close_window(id);
-                    }
-                    start_timer();
-                },
-                class: "w-32 h-12 text-xl text-red-200",
-                text: if is_focus_mode {
-                    "Start focus"
-                } else {
-                    "Start break"
+            div {
+                class: "flex space-x-2",
+                Button {
+                    title: "Remind me again in 5 minutes",
+                    action: snooze,
+                    class: "w-32 h-12 text-xl text-red-200",
+                    text: "Snooze",
+                }
+                Button {
+                    title: "Start timer",
+                    action: start_timer,
+                    class: "w-32 h-12 text-xl text-red-200",
+                    text: start_label(&phase),
                 }
             }
         }
@@ -0,0 +1,48 @@
+use dioxus::prelude::*;
+
+use crate::{
+    components::timer::start_first_focus,
+    schedule::WORK_START_PROMPT,
+    state::{BG_COLOR, TEXT_COLOR_INVERTED},
+    ui::button::Button,
+};
+
+fn start_working() {
+    *WORK_START_PROMPT.write() = false;
+    start_first_focus();
+}
+
+fn dismiss_work_start_prompt() {
+    *WORK_START_PROMPT.write() = false;
+}
+
+#[component]
+pub fn WorkStartPrompt() -> Element {
+    let bg_color = *BG_COLOR.read();
+    let text_color_inverted = *TEXT_COLOR_INVERTED.read();
+
+    rsx! {
+        div {
+            class: format!("absolute top-0 left-0 w-full h-full z-30 flex flex-col items-center justify-center text-center space-y-8 {}", bg_color),
+            h1 {
+                class: "text-6xl font-bold",
+                "Working hours have started"
+            }
+            div {
+                class: "flex space-x-4",
+                Button {
+                    title: "Start the first focus session",
+                    action: start_working,
+                    class: format!("w-32 h-12 text-xl {}", text_color_inverted),
+                    text: "Start focus",
+                }
+                Button {
+                    title: "Start later",
+                    action: dismiss_work_start_prompt,
+                    class: format!("w-32 h-12 text-xl {}", text_color_inverted),
+                    text: "Later",
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use dioxus::signals::Readable;
+
+use crate::{
+    settings::Phase,
+    state::{FULL_SESSION_COUNT, PHASE_INDEX, SMALL_SESSION_COUNT},
+};
+
+/// Where in the session sequence the timer is and how much of it was done.
+/// The counters only change by moving through the sequence, so undoing a move restores them exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cycle {
+    /// Position in the sequence, see `Settings::phases`
+    pub index: usize,
+    /// Focus phases moved past
+    pub sessions: u32,
+    /// Times the sequence was run through to its end
+    pub cycles: u32,
+}
+
+impl Cycle {
+    /// The cycle as shown by the timer
+    pub fn current() -> Self {
+        Self {
+            index: *PHASE_INDEX.peek(),
+            sessions: *SMALL_SESSION_COUNT.peek(),
+            cycles: *FULL_SESSION_COUNT.peek(),
+        }
+    }
+
+    /// Makes this the cycle shown by the timer
+    pub fn apply(self) {
+        *PHASE_INDEX.write() = self.index;
+        *SMALL_SESSION_COUNT.write() = self.sessions;
+        *FULL_SESSION_COUNT.write() = self.cycles;
+    }
+
+    /// Moves past the current phase of `phases`, a focus phase counts as a session
+    /// and wrapping around to the first phase completes a cycle
+    pub fn advance(self, phases: &[Phase]) -> Self {
+        if phases.is_empty() {
+            return self;
+        }
+        let index = self.index % phases.len();
+        let next = (index + 1) % phases.len();
+
+        Self {
+            index: next,
+            sessions: self.sessions + phases[index].is_focus() as u32,
+            cycles: self.cycles + (next == 0) as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{history::SessionKind, settings::PhaseColor};
+
+    fn phases(kinds: &[SessionKind]) -> Vec<Phase> {
+        kinds
+            .iter()
+            .map(|&kind| Phase::new("Phase", 25, kind, PhaseColor::Blue))
+            .collect()
+    }
+
+    #[test]
+    fn counts_focus_sessions_and_whole_sequences() {
+        use SessionKind::*;
+        let phases = phases(&[Focus, ShortBreak, Focus, LongBreak]);
+
+        let mut cycle = Cycle::default();
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            cycle = cycle.advance(&phases);
+            seen.push((cycle.index, cycle.sessions, cycle.cycles));
+        }
+        assert_eq!(
+            seen,
+            [(1, 1, 0), (2, 1, 0), (3, 2, 0), (0, 2, 1), (1, 3, 1)]
+        );
+    }
+
+    #[test]
+    fn stays_put_without_phases() {
+        let cycle = Cycle {
+            index: 2,
+            sessions: 1,
+            cycles: 0,
+        };
+        assert_eq!(cycle.advance(&[]), cycle);
+    }
+
+    fn any_phases() -> impl Strategy<Value = Vec<Phase>> {
+        let kind = prop::sample::select(SessionKind::ALL.to_vec());
+        prop::collection::vec(kind, 1..8).prop_map(|kinds| phases(&kinds))
+    }
+
+    proptest! {
+        #[test]
+        fn the_counters_follow_from_the_moves(phases in any_phases(), moves in 0usize..100) {
+            let mut cycle = Cycle::default();
+            for _ in 0..moves {
+                cycle = cycle.advance(&phases);
+            }
+
+            let focus_per_cycle = phases.iter().filter(|phase| phase.is_focus()).count();
+            let index = moves % phases.len();
+            let focus_in_cycle = phases[..index].iter().filter(|phase| phase.is_focus()).count();
+            prop_assert_eq!(
+                cycle,
+                Cycle {
+                    index,
+                    sessions: (moves / phases.len() * focus_per_cycle + focus_in_cycle) as u32,
+                    cycles: (moves / phases.len()) as u32,
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    process::{ExitStatus, Stdio},
+    time::Duration,
+};
+
+use dioxus::prelude::*;
+use tokio::{process::Command, sync::broadcast::error::RecvError};
+use tracing::{error, info};
+
+use crate::{
+    bus::{subscribe_events, SessionEvent},
+    settings::SETTINGS,
+};
+
+/// Runs the configured shell command for every session event
+pub fn use_event_hooks() {
+    use_future(|| async {
+        let mut events = subscribe_events();
+
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let settings = SETTINGS.peek();
+                    let Some(command) = settings
+                        .hooks
+                        .get(&event.kind)
+                        .map(|command| command.trim().to_string())
+                        .filter(|command| !command.is_empty())
+                    else {
+                        continue;
+                    };
+                    let timeout = Duration::from_secs(settings.hook_timeout_secs.into());
+                    drop(settings);
+
+                    // on its own task, so a slow hook never holds up the timer
+                    tokio::spawn(run_hook(command, event, timeout));
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    error!("Skipped {} session events, hooks were too slow", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    }
+}
+
+async fn run_hook(command: String, event: SessionEvent, timeout: Duration) {
+    let name = event.kind.name();
+    match execute_hook(&command, &event, timeout).await {
+        Ok(status) => info!("{} hook finished: {}", name, status),
+        Err(e) => error!("{}", e),
+    }
+}
+
+/// Runs `command` with the details of `event` in its environment, it's killed after `timeout`
+async fn execute_hook(
+    command: &str,
+    event: &SessionEvent,
+    timeout: Duration,
+) -> Result<ExitStatus, String> {
+    let name = event.kind.name();
+    let task = event.task.as_ref();
+
+    let mut hook = shell_command(command);
+    hook.env("AURATIMER_EVENT", name)
+        .env("AURATIMER_PHASE", event.phase.name())
+        .env("AURATIMER_PHASE_NAME", &event.phase_name)
+        .env("AURATIMER_PLANNED_SECONDS", event.planned_secs.to_string())
+        .env(
+            "AURATIMER_REMAINING_SECONDS",
+            event.remaining_secs.to_string(),
+        )
+        .env("AURATIMER_SESSION_NUMBER", event.session_number.to_string())
+        .env("AURATIMER_TASK", task.map_or("", |t| t.name.as_str()))
+        .env(
+            "AURATIMER_PROJECT",
+            task.and_then(|t| t.project.as_deref()).unwrap_or(""),
+        )
+        .env(
+            "AURATIMER_TAG",
+            task.and_then(|t| t.tag.as_deref()).unwrap_or(""),
+        )
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        // the child is killed when the timeout drops it
+        .kill_on_drop(true);
+
+    let mut child = hook
+        .spawn()
+        .map_err(|e| format!("Failed to run {} hook \"{}\": {}", name, command, e))?;
+
+    match tokio::time::timeout(timeout, child.wait()).await {
+        Ok(Ok(status)) if status.success() => Ok(status),
+        Ok(Ok(status)) => Err(format!("{} hook \"{}\" failed: {}", name, command, status)),
+        Ok(Err(e)) => Err(format!(
+            "Failed to wait for {} hook \"{}\": {}",
+            name, command, e
+        )),
+        Err(_) => {
+            if let Err(e) = child.kill().await {
+                error!("Failed to kill {} hook: {}", name, e);
+            }
+            Err(format!(
+                "{} hook \"{}\" timed out after {}s, killed it",
+                name,
+                command,
+                timeout.as_secs()
+            ))
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{collections::HashMap, fs, time::Instant};
+
+    use chrono::Local;
+
+    use super::*;
+    use crate::{
+        bus::SessionEventKind,
+        history::{SessionKind, TaskLabel},
+    };
+
+    fn event() -> SessionEvent {
+        SessionEvent {
+            kind: SessionEventKind::FocusEnd,
+            at: Local::now(),
+            phase: SessionKind::Focus,
+            phase_name: "Deep work".to_string(),
+            planned_secs: 1500,
+            remaining_secs: 0,
+            task: TaskLabel::parse("Write report @work"),
+            session_number: 3,
+        }
+    }
+
+    #[tokio::test]
+    async fn the_hook_gets_the_event_in_its_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("env");
+        let command = format!("env > '{}'", out.display());
+
+        let result = execute_hook(&command, &event(), Duration::from_secs(10)).await;
+        assert!(result.is_ok());
+
+        let contents = fs::read_to_string(&out).unwrap();
+        let env: HashMap<&str, &str> = contents
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .filter(|(key, _)| key.starts_with("AURATIMER_"))
+            .collect();
+        assert_eq!(
+            env,
+            HashMap::from([
+                ("AURATIMER_EVENT", "focus_end"),
+                ("AURATIMER_PHASE", "focus"),
+                ("AURATIMER_PHASE_NAME", "Deep work"),
+                ("AURATIMER_PLANNED_SECONDS", "1500"),
+                ("AURATIMER_REMAINING_SECONDS", "0"),
+                ("AURATIMER_SESSION_NUMBER", "3"),
+                ("AURATIMER_TASK", "Write report"),
+                ("AURATIMER_PROJECT", "work"),
+                ("AURATIMER_TAG", ""),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn a_failing_hook_is_reported() {
+        let result = execute_hook("exit 3", &event(), Duration::from_secs(10)).await;
+        assert!(result.is_err_and(|e| e.starts_with("focus_end hook \"exit 3\" failed")));
+    }
+
+    #[tokio::test]
+    async fn a_hook_is_killed_when_it_takes_too_long() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("finished");
+        let command = format!("sleep 1 && touch '{}'", out.display());
+
+        let started = Instant::now();
+        let result = execute_hook(&command, &event(), Duration::from_millis(100)).await;
+        assert!(result.is_err_and(|e| e.contains("timed out")));
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!out.exists());
+    }
+}
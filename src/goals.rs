@@ -0,0 +1,81 @@
+use dioxus::prelude::*;
+
+use crate::{
+    components::{
+        goal_alert::{GoalAlert, GoalAlertProps},
+        timer::show_alert_window,
+    },
+    history::{focus_since, HISTORY},
+    schedule::DAY_START,
+    settings::{DailyGoal, SETTINGS},
+};
+
+/// Progress towards the daily goal
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GoalProgress {
+    pub goal: DailyGoal,
+    pub done: u32,
+    pub target: u32,
+}
+
+impl GoalProgress {
+    pub fn reached(&self) -> bool {
+        self.done >= self.target
+    }
+
+    /// e.g. "3/8 sessions"
+    pub fn summary(&self) -> String {
+        match self.goal {
+            DailyGoal::Minutes => format!("{}/{} min", self.done, self.target),
+            _ => format!("{}/{} sessions", self.done, self.target),
+        }
+    }
+}
+
+/// Progress of today, `None` without a daily goal
+pub fn goal_progress() -> Option<GoalProgress> {
+    let settings = SETTINGS.read();
+    let totals = focus_since(HISTORY.read().iter(), *DAY_START.read());
+
+    let (done, target) = match settings.daily_goal {
+        DailyGoal::Off => return None,
+        DailyGoal::Sessions => (totals.sessions, settings.daily_goal_sessions),
+        DailyGoal::Minutes => (totals.focus_secs / 60, settings.daily_goal_minutes),
+    };
+    Some(GoalProgress {
+        goal: settings.daily_goal,
+        done,
+        target,
+    })
+}
+
+/// Shows an alert once a day when the daily goal is reached, used once in the main window
+pub fn use_goal_notification() {
+    // a goal reached before launch was already announced
+    let mut announced = use_signal(|| {
+        goal_progress()
+            .filter(GoalProgress::reached)
+            .map(|_| *DAY_START.peek())
+    });
+
+    use_effect(move || {
+        let Some(progress) = goal_progress().filter(GoalProgress::reached) else {
+            return;
+        };
+        let day = *DAY_START.read();
+        if *announced.peek() == Some(day) {
+            return;
+        }
+
+        announced.set(Some(day));
+        let summary = progress.summary();
+        show_alert_window(move || {
+            VirtualDom::new_with_props(
+                GoalAlert,
+                GoalAlertProps {
+                    summary: summary.clone(),
+                },
+            )
+        });
+    });
+}
@@ -0,0 +1,599 @@
+use std::{
+    fmt, fs, io,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
+use dioxus::{
+    prelude::use_future,
+    signals::{GlobalSignal, Readable},
+};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    interruptions::Interruption, settings::SETTINGS, storage::write_json, tasks::active_task_label,
+};
+
+pub static HISTORY: GlobalSignal<Vec<SessionRecord>> = GlobalSignal::new(load_history);
+/// Task the upcoming and running focus sessions are attributed to
+pub static CURRENT_TASK: GlobalSignal<Option<TaskLabel>> = GlobalSignal::new(active_task_label);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionKind {
+    Focus,
+    ShortBreak,
+    LongBreak,
+}
+
+impl SessionKind {
+    pub const ALL: [SessionKind; 3] = [
+        SessionKind::Focus,
+        SessionKind::ShortBreak,
+        SessionKind::LongBreak,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionKind::Focus => "Focus",
+            SessionKind::ShortBreak => "Short break",
+            SessionKind::LongBreak => "Long break",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionKind::Focus => "focus",
+            SessionKind::ShortBreak => "short_break",
+            SessionKind::LongBreak => "long_break",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        SessionKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionOutcome {
+    Completed,
+    Skipped,
+    /// A strict break skipped through the emergency skip
+    EmergencySkipped,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskLabel {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+impl TaskLabel {
+    /// Parses labels like "Write report @work #writing", returns `None` if there is no task name
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut label = TaskLabel::default();
+        let mut name = Vec::new();
+
+        for word in input.split_whitespace() {
+            if let Some(project) = word.strip_prefix('@').filter(|p| !p.is_empty()) {
+                label.project = Some(project.to_string());
+            } else if let Some(tag) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+                label.tag = Some(tag.to_string());
+            } else {
+                name.push(word);
+            }
+        }
+
+        if name.is_empty() {
+            return None;
+        }
+        label.name = name.join(" ");
+        Some(label)
+    }
+}
+
+impl fmt::Display for TaskLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(project) = &self.project {
+            write!(f, " @{}", project)?;
+        }
+        if let Some(tag) = &self.tag {
+            write!(f, " #{}", tag)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Identifies the record while it's edited, 0 until it's added to the history and in older files
+    #[serde(default)]
+    pub id: u64,
+    pub kind: SessionKind,
+    pub started_at: DateTime<Local>,
+    pub ended_at: DateTime<Local>,
+    /// Time the timer actually ran, excluding pauses
+    pub duration_secs: u32,
+    /// false if the session was skipped before it ran out
+    pub completed: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub emergency_skip: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<TaskLabel>,
+    /// Name of the timer preset in use, if the durations matched one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// Added or changed by hand rather than recorded by the timer
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub manual: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interruptions: Vec<Interruption>,
+}
+
+impl SessionRecord {
+    /// A session entered by hand, e.g. because the timer wasn't started
+    pub fn manual(
+        kind: SessionKind,
+        started_at: DateTime<Local>,
+        ended_at: DateTime<Local>,
+        task: Option<TaskLabel>,
+    ) -> Result<Self, String> {
+        if ended_at <= started_at {
+            return Err("The session must end after it starts".to_string());
+        }
+        if ended_at > Local::now() {
+            return Err("The session can't end in the future".to_string());
+        }
+
+        Ok(Self {
+            id: 0,
+            kind,
+            started_at,
+            ended_at,
+            duration_secs: (ended_at - started_at)
+                .num_seconds()
+                .try_into()
+                .unwrap_or(u32::MAX),
+            completed: true,
+            emergency_skip: false,
+            task: if kind == SessionKind::Focus {
+                task
+            } else {
+                None
+            },
+            preset: None,
+            manual: true,
+            interruptions: Vec::new(),
+        })
+    }
+
+    /// This session with the times, kind and task of `changes`, the time it ran is kept if the times didn't change
+    pub fn edited(&self, changes: SessionRecord) -> Self {
+        let times_changed =
+            changes.started_at != self.started_at || changes.ended_at != self.ended_at;
+
+        Self {
+            id: self.id,
+            duration_secs: if times_changed {
+                changes.duration_secs
+            } else {
+                self.duration_secs
+            },
+            preset: self.preset.clone(),
+            completed: self.completed,
+            interruptions: self.interruptions.clone(),
+            emergency_skip: self.emergency_skip,
+            ..changes
+        }
+    }
+
+    fn overlaps(&self, other: &SessionRecord) -> bool {
+        self.started_at < other.ended_at && other.started_at < self.ended_at
+    }
+}
+
+/// One more than the highest id, taken while changing the history so it's unique among the records
+fn next_record_id(history: &[SessionRecord]) -> u64 {
+    history.iter().map(|record| record.id).max().unwrap_or(0) + 1
+}
+
+/// Builds a manual session from form or command line input like "2025-06-02", "9:00" and "9:25",
+/// an end time before the start time is on the next day
+pub fn parse_manual_session(
+    kind: SessionKind,
+    date: &str,
+    start: &str,
+    end: &str,
+    task: &str,
+) -> Result<SessionRecord, String> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("\"{}\" isn't a date like 2025-06-02", date.trim()))?;
+    let parse_time = |time: &str| {
+        NaiveTime::parse_from_str(time.trim(), "%H:%M")
+            .map_err(|_| format!("\"{}\" isn't a time like 9:25", time.trim()))
+    };
+    let start = parse_time(start)?;
+    let end = parse_time(end)?;
+
+    let end_date = if end <= start {
+        date.succ_opt().ok_or("Invalid date")?
+    } else {
+        date
+    };
+    let to_local = |date: NaiveDate, time: NaiveTime| {
+        date.and_time(time)
+            .and_local_timezone(Local)
+            .earliest()
+            .ok_or(format!(
+                "{} doesn't exist on {}",
+                time.format("%H:%M"),
+                date
+            ))
+    };
+
+    SessionRecord::manual(
+        kind,
+        to_local(date, start)?,
+        to_local(end_date, end)?,
+        TaskLabel::parse(task),
+    )
+}
+
+/// Puts `record` into the history in order of its start, in place of `replacing` when editing.
+/// Fails if it overlaps another session.
+pub fn insert_record(
+    history: &mut Vec<SessionRecord>,
+    record: SessionRecord,
+    replacing: Option<usize>,
+) -> Result<(), String> {
+    let overlapping = history
+        .iter()
+        .enumerate()
+        .find(|(i, other)| Some(*i) != replacing && record.overlaps(other));
+    if let Some((_, other)) = overlapping {
+        return Err(format!(
+            "Overlaps the {} session from {} to {}",
+            other.kind.label().to_lowercase(),
+            other.started_at.format("%Y-%m-%d %H:%M"),
+            other.ended_at.format("%H:%M")
+        ));
+    }
+
+    if let Some(index) = replacing {
+        history.remove(index);
+    }
+    let mut record = record;
+    if record.id == 0 {
+        record.id = next_record_id(history);
+    }
+    let position = history.partition_point(|other| other.started_at <= record.started_at);
+    history.insert(position, record);
+    Ok(())
+}
+
+/// Focus time and session count of a single task
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskStats {
+    pub task: Option<TaskLabel>,
+    pub focus_secs: u32,
+    pub sessions: u32,
+    pub interruptions: u32,
+}
+
+/// Focus done since some point in time, e.g. the start of the day
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FocusTotals {
+    /// Completed focus sessions
+    pub sessions: u32,
+    /// Includes skipped sessions
+    pub focus_secs: u32,
+}
+
+fn history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("AuraTimer").join("history.json"))
+}
+
+fn parse_history(contents: &str) -> serde_json::Result<Vec<SessionRecord>> {
+    let mut history: Vec<SessionRecord> = serde_json::from_str(contents)?;
+    // sessions don't overlap, so the start is unique and stays the same every time the file is read
+    for record in history.iter_mut().filter(|record| record.id == 0) {
+        record.id = record.started_at.timestamp_millis() as u64;
+    }
+    Ok(history)
+}
+
+/// Like `load_history`, but failing instead of starting over when the file can't be read.
+/// A missing file is an empty history.
+pub fn read_history() -> Result<Vec<SessionRecord>, Box<dyn std::error::Error>> {
+    let path = history_path().ok_or("No data directory available")?;
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(parse_history(&contents)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn load_history() -> Vec<SessionRecord> {
+    read_history().unwrap_or_else(|e| {
+        error!("Failed to load session history: {}", e);
+        Vec::new()
+    })
+}
+
+pub fn save_history(history: &[SessionRecord]) -> Result<(), Box<dyn std::error::Error>> {
+    let path = history_path().ok_or("No data directory available")?;
+    write_json(&path, &history)
+}
+
+/// Applies `change` to the history as saved rather than as loaded, so sessions changed by the `history` command
+/// in the meantime are kept, then saves and shows the result
+fn update_history(
+    change: impl FnOnce(&mut Vec<SessionRecord>) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut history = read_history().unwrap_or_else(|e| {
+        error!(
+            "Failed to read session history, keeping the loaded one: {}",
+            e
+        );
+        HISTORY.peek().clone()
+    });
+    change(&mut history)?;
+
+    if let Err(e) = save_history(&history) {
+        error!("Failed to save session history: {}", e);
+    }
+    if *HISTORY.peek() != history {
+        *HISTORY.write() = history;
+    }
+    Ok(())
+}
+
+/// Adds a finished or skipped session to the history, focus sessions are attributed to the current task
+pub fn record_session(
+    kind: SessionKind,
+    started_at: DateTime<Local>,
+    duration_secs: u32,
+    outcome: SessionOutcome,
+    interruptions: Vec<Interruption>,
+) -> SessionRecord {
+    let task = if kind == SessionKind::Focus {
+        CURRENT_TASK.peek().clone()
+    } else {
+        None
+    };
+
+    let record = SessionRecord {
+        id: 0,
+        kind,
+        started_at,
+        ended_at: Local::now(),
+        duration_secs,
+        completed: outcome == SessionOutcome::Completed,
+        emergency_skip: outcome == SessionOutcome::EmergencySkipped,
+        task,
+        preset: SETTINGS
+            .peek()
+            .current_preset()
+            .map(|preset| preset.name.clone()),
+        manual: false,
+        interruptions,
+    };
+    add_record(record)
+}
+
+/// Appends `record` unless it's already there, with a new id if it has none yet or another record took its id
+fn append_record(history: &mut Vec<SessionRecord>, mut record: SessionRecord) -> SessionRecord {
+    if history.contains(&record) {
+        return record;
+    }
+    if record.id == 0 || history.iter().any(|r| r.id == record.id) {
+        record.id = next_record_id(history);
+    }
+    history.push(record.clone());
+    record
+}
+
+/// Appends a record to the history, also used to put it back when a skip is redone.
+/// Returns it with the id it got.
+pub fn add_record(record: SessionRecord) -> SessionRecord {
+    let mut added = record.clone();
+    let _ = update_history(|history| {
+        added = append_record(history, record);
+        Ok(())
+    });
+    added
+}
+
+/// Removes `record` even if it was edited since, e.g. when a skipped session is restored or a session is deleted
+pub fn remove_record(record: &SessionRecord) {
+    let _ = update_history(|history| {
+        history.retain(|r| r.id != record.id);
+        Ok(())
+    });
+}
+
+/// Adds a session entered by hand, unless it overlaps another one
+pub fn add_manual_record(record: SessionRecord) -> Result<(), String> {
+    update_history(|history| insert_record(history, record, None))
+}
+
+/// Replaces `original` with its edited version
+pub fn edit_record(original: &SessionRecord, changes: SessionRecord) -> Result<(), String> {
+    update_history(|history| {
+        let index = history
+            .iter()
+            .position(|r| r.id == original.id)
+            .ok_or("The session is no longer in the history")?;
+        let record = history[index].edited(changes);
+        insert_record(history, record, Some(index))
+    })
+}
+
+/// Reloads the history when it's changed outside the app, e.g. by the `history` command
+pub fn use_history_watcher() {
+    use_future(|| async {
+        let mut interval = tokio::time::interval(Duration::from_secs(2));
+        let modified =
+            || history_path().and_then(|path| fs::metadata(path).and_then(|m| m.modified()).ok());
+        let mut last_modified: Option<SystemTime> = modified();
+
+        loop {
+            interval.tick().await;
+
+            let current = modified();
+            if current == last_modified {
+                continue;
+            }
+            last_modified = current;
+
+            // keep what's loaded while the file is half written or broken
+            let history = match read_history() {
+                Ok(history) => history,
+                Err(e) => {
+                    error!("Failed to reload session history: {}", e);
+                    continue;
+                }
+            };
+            // the app's own saves change the file too
+            if *HISTORY.peek() != history {
+                *HISTORY.write() = history;
+            }
+        }
+    });
+}
+
+/// Sums up the focus sessions that ended after `since`
+pub fn focus_since<'a>(
+    records: impl IntoIterator<Item = &'a SessionRecord>,
+    since: DateTime<Local>,
+) -> FocusTotals {
+    let mut totals = FocusTotals::default();
+
+    for record in records {
+        if record.kind != SessionKind::Focus || record.ended_at < since {
+            continue;
+        }
+        totals.focus_secs += record.duration_secs;
+        if record.completed {
+            totals.sessions += 1;
+        }
+    }
+    totals
+}
+
+/// Sums up focus sessions per task, most focused task first
+pub fn stats_by_task<'a>(records: impl IntoIterator<Item = &'a SessionRecord>) -> Vec<TaskStats> {
+    let mut stats: Vec<TaskStats> = Vec::new();
+
+    for record in records {
+        if record.kind != SessionKind::Focus {
+            continue;
+        }
+
+        match stats.iter_mut().find(|s| s.task == record.task) {
+            Some(task_stats) => {
+                task_stats.focus_secs += record.duration_secs;
+                task_stats.sessions += 1;
+                task_stats.interruptions += record.interruptions.len() as u32;
+            }
+            None => stats.push(TaskStats {
+                task: record.task.clone(),
+                focus_secs: record.duration_secs,
+                sessions: 1,
+                interruptions: record.interruptions.len() as u32,
+            }),
+        }
+    }
+
+    stats.sort_by_key(|s| std::cmp::Reverse(s.focus_secs));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn session(minutes_ago: i64) -> SessionRecord {
+        let ended_at = Local::now() - TimeDelta::minutes(minutes_ago);
+        SessionRecord::manual(
+            SessionKind::Focus,
+            ended_at - TimeDelta::minutes(25),
+            ended_at,
+            TaskLabel::parse("Write report"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn older_records_get_the_same_ids_on_every_read() {
+        // written before records had ids
+        let history = vec![session(60), session(30)];
+        let contents = serde_json::to_string(&history).unwrap();
+
+        let first = parse_history(&contents).unwrap();
+        assert_eq!(first, parse_history(&contents).unwrap());
+        assert!(first.iter().all(|record| record.id != 0));
+        assert_ne!(first[0].id, first[1].id);
+    }
+
+    #[test]
+    fn new_records_get_ids_no_other_record_has() {
+        let mut history = Vec::new();
+        let first = append_record(&mut history, session(90));
+        let second = append_record(&mut history, session(60));
+        assert_eq!((first.id, second.id), (1, 2));
+
+        // adding the same record again, e.g. redoing a skip twice, changes nothing
+        assert_eq!(append_record(&mut history, second.clone()), second);
+        assert_eq!(history.len(), 2);
+
+        // a skip that is undone and redone after its id was given to another session gets a new one
+        history.retain(|record| record.id != second.id);
+        let third = append_record(&mut history, session(30));
+        assert_eq!(third.id, second.id);
+        let redone = append_record(&mut history, second);
+        assert_eq!(redone.id, 3);
+        assert_eq!(
+            history.iter().map(|record| record.id).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn edits_keep_the_id() {
+        let mut history = Vec::new();
+        insert_record(&mut history, session(60), None).unwrap();
+        insert_record(&mut history, session(30), None).unwrap();
+        let original = history[0].clone();
+        let other = history[1].clone();
+        assert_ne!(original.id, other.id);
+
+        let changes = parse_manual_session(
+            SessionKind::ShortBreak,
+            &original.started_at.format("%Y-%m-%d").to_string(),
+            &original.started_at.format("%H:%M").to_string(),
+            &(original.started_at + TimeDelta::minutes(5))
+                .format("%H:%M")
+                .to_string(),
+            "",
+        )
+        .unwrap();
+        let edited = history[0].edited(changes.clone());
+        insert_record(&mut history, edited, Some(0)).unwrap();
+
+        let expected = SessionRecord {
+            id: original.id,
+            ..changes
+        };
+        assert_eq!(history, [expected, other]);
+    }
+}
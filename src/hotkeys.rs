@@ -0,0 +1,160 @@
+use std::{collections::BTreeMap, str::FromStr, sync::Mutex};
+
+use dioxus::{
+    desktop::{window, ShortcutHandle, ShortcutRegistryError},
+    prelude::*,
+};
+use global_hotkey::hotkey::{Code, HotKey, Modifiers};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    bus::{send_command, TimerCommand, WindowCommand},
+    settings::SETTINGS,
+    shortcuts::KeyCombo,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GlobalHotkeyAction {
+    ToggleTimer,
+    SkipSession,
+    ShowWindow,
+    LogInterruption,
+}
+
+impl GlobalHotkeyAction {
+    pub const ALL: [GlobalHotkeyAction; 4] = [
+        GlobalHotkeyAction::ToggleTimer,
+        GlobalHotkeyAction::SkipSession,
+        GlobalHotkeyAction::ShowWindow,
+        GlobalHotkeyAction::LogInterruption,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            GlobalHotkeyAction::ToggleTimer => "Start / pause timer",
+            GlobalHotkeyAction::SkipSession => "Skip session",
+            GlobalHotkeyAction::ShowWindow => "Show window",
+            GlobalHotkeyAction::LogInterruption => "Log an interruption",
+        }
+    }
+
+    pub fn default_binding(&self) -> &'static str {
+        match self {
+            GlobalHotkeyAction::ToggleTimer => "CmdOrCtrl+Alt+P",
+            GlobalHotkeyAction::SkipSession => "CmdOrCtrl+Alt+S",
+            GlobalHotkeyAction::ShowWindow => "CmdOrCtrl+Alt+A",
+            GlobalHotkeyAction::LogInterruption => "CmdOrCtrl+Alt+I",
+        }
+    }
+}
+
+pub fn default_global_hotkeys() -> BTreeMap<GlobalHotkeyAction, String> {
+    GlobalHotkeyAction::ALL
+        .into_iter()
+        .map(|action| (action, action.default_binding().to_string()))
+        .collect()
+}
+
+/// Hotkeys the OS refused to register, shown in the settings
+pub static HOTKEY_ERRORS: GlobalSignal<Vec<String>> = GlobalSignal::new(Vec::new);
+
+thread_local! {
+    static HOTKEY_HANDLES: Mutex<Vec<ShortcutHandle>> = const { Mutex::new(Vec::new()) };
+}
+
+fn to_hotkey(combo: &KeyCombo) -> Result<HotKey, String> {
+    let mut modifiers = Modifiers::empty();
+    if combo.meta {
+        modifiers |= Modifiers::SUPER;
+    }
+    if combo.ctrl {
+        modifiers |= Modifiers::CONTROL;
+    }
+    if combo.alt {
+        modifiers |= Modifiers::ALT;
+    }
+    if combo.shift {
+        modifiers |= Modifiers::SHIFT;
+    }
+
+    let code = Code::from_str(&combo.code)
+        .map_err(|_| format!("\"{}\" can't be used as a global hotkey", combo.code))?;
+
+    Ok(HotKey::new(Some(modifiers), code))
+}
+
+// hotkey callbacks run outside of the dioxus runtime, so they go through the bus like the tray
+fn trigger(action: GlobalHotkeyAction) {
+    match action {
+        GlobalHotkeyAction::ToggleTimer => send_command(TimerCommand::Toggle),
+        GlobalHotkeyAction::SkipSession => send_command(TimerCommand::Skip),
+        GlobalHotkeyAction::ShowWindow => send_command(WindowCommand::Show),
+        GlobalHotkeyAction::LogInterruption => {
+            // the prompt is in the main window
+            send_command(WindowCommand::Show);
+            send_command(TimerCommand::Interrupt(None));
+        }
+    }
+}
+
+/// (Re-)registers the global hotkeys from the settings, replacing previously registered ones
+pub fn register_global_hotkeys() {
+    let handle = window();
+
+    HOTKEY_HANDLES.with(|handles| {
+        for shortcut in handles.lock().unwrap().drain(..) {
+            handle.remove_shortcut(shortcut);
+        }
+    });
+
+    let mut errors = Vec::new();
+
+    for action in GlobalHotkeyAction::ALL {
+        let binding = SETTINGS.peek().global_hotkey(action).to_string();
+        // an empty binding disables the hotkey
+        if binding.is_empty() {
+            continue;
+        }
+
+        let hotkey = match binding
+            .parse::<KeyCombo>()
+            .and_then(|combo| to_hotkey(&combo))
+        {
+            Ok(hotkey) => hotkey,
+            Err(e) => {
+                errors.push(format!("{}: {}", action.description(), e));
+                continue;
+            }
+        };
+
+        match handle.create_shortcut(hotkey, move || trigger(action)) {
+            Ok(shortcut) => {
+                HOTKEY_HANDLES.with(|handles| handles.lock().unwrap().push(shortcut));
+            }
+            Err(e) => {
+                let reason = match e {
+                    ShortcutRegistryError::InvalidShortcut(shortcut) => {
+                        format!("\"{}\" is not a valid hotkey", shortcut)
+                    }
+                    ShortcutRegistryError::Other(e) => e.to_string(),
+                    _ => "unknown error".to_string(),
+                };
+                error!(
+                    "Failed to register global hotkey {} for {}: {}",
+                    binding,
+                    action.description(),
+                    reason
+                );
+                errors.push(format!(
+                    "{} ({}) could not be registered, it may be in use by another app: {}",
+                    action.description(),
+                    binding,
+                    reason
+                ));
+            }
+        }
+    }
+
+    *HOTKEY_ERRORS.write() = errors;
+}
@@ -0,0 +1,345 @@
+use std::time::{Duration, Instant};
+
+use dioxus::prelude::*;
+
+use crate::{
+    bus::{send_command, WindowCommand},
+    components::timer::{pause_for_idle, TIMER_RUNNING},
+    settings::SETTINGS,
+    state::PHASE_INDEX,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long the user was away after an idle pause, shown once they're back
+pub static AWAY_PROMPT: GlobalSignal<Option<Duration>> = GlobalSignal::new(|| None);
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::time::Duration;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceSecondsSinceLastEventType(state_id: i32, event_type: u32) -> f64;
+    }
+    const COMBINED_SESSION_STATE: i32 = 0;
+    const ANY_INPUT_EVENT: u32 = u32::MAX;
+
+    pub struct IdleSource;
+
+    impl IdleSource {
+        pub async fn new() -> Self {
+            IdleSource
+        }
+
+        pub async fn idle_time(&self) -> Option<Duration> {
+            let secs = unsafe {
+                CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, ANY_INPUT_EVENT)
+            };
+            Duration::try_from_secs_f64(secs).ok()
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use tracing::error;
+    use zbus::{proxy, proxy::CacheProperties, Connection};
+
+    use crate::logind::LoginSessionProxy;
+
+    #[proxy(
+        interface = "org.gnome.Mutter.IdleMonitor",
+        default_service = "org.gnome.Mutter.IdleMonitor",
+        default_path = "/org/gnome/Mutter/IdleMonitor/Core"
+    )]
+    trait MutterIdleMonitor {
+        fn get_idletime(&self) -> zbus::Result<u64>;
+    }
+
+    #[proxy(
+        interface = "org.freedesktop.ScreenSaver",
+        default_service = "org.freedesktop.ScreenSaver",
+        default_path = "/org/freedesktop/ScreenSaver"
+    )]
+    trait ScreenSaver {
+        fn get_session_idle_time(&self) -> zbus::Result<u32>;
+    }
+
+    /// Asks GNOME, then the freedesktop screensaver, then logind, whichever is available
+    pub struct IdleSource {
+        pub(super) session: Option<Connection>,
+        pub(super) system: Option<Connection>,
+    }
+
+    impl IdleSource {
+        pub async fn new() -> Self {
+            let session = Connection::session()
+                .await
+                .inspect_err(|e| error!("Failed to connect to the session bus: {}", e))
+                .ok();
+            let system = Connection::system()
+                .await
+                .inspect_err(|e| error!("Failed to connect to the system bus: {}", e))
+                .ok();
+            Self { session, system }
+        }
+
+        pub async fn idle_time(&self) -> Option<Duration> {
+            if let Some(session) = &self.session {
+                let mutter = async {
+                    MutterIdleMonitorProxy::new(session)
+                        .await?
+                        .get_idletime()
+                        .await
+                };
+                if let Ok(millis) = mutter.await {
+                    return Some(Duration::from_millis(millis));
+                }
+
+                let screen_saver = async {
+                    ScreenSaverProxy::new(session)
+                        .await?
+                        .get_session_idle_time()
+                        .await
+                };
+                // seconds as in the freedesktop spec
+                if let Ok(secs) = screen_saver.await {
+                    return Some(Duration::from_secs(secs.into()));
+                }
+            }
+
+            let login_session = LoginSessionProxy::builder(self.system.as_ref()?)
+                .cache_properties(CacheProperties::No)
+                .build()
+                .await
+                .ok()?;
+            if !login_session.idle_hint().await.ok()? {
+                return Some(Duration::ZERO);
+            }
+            let idle_since = Duration::from_micros(login_session.idle_since_hint().await.ok()?);
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+            Some(now.saturating_sub(idle_since))
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+mod platform {
+    use std::time::Duration;
+
+    pub struct IdleSource;
+
+    impl IdleSource {
+        pub async fn new() -> Self {
+            IdleSource
+        }
+
+        pub async fn idle_time(&self) -> Option<Duration> {
+            None
+        }
+    }
+}
+
+/// What a poll of the idle time leads to
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum IdleChange {
+    None,
+    /// Idle for the configured time during a running focus session, it gets paused
+    Away,
+    /// Active again after an idle pause
+    Back,
+    /// The paused session was resumed by hand, e.g. through a global hotkey
+    Resumed,
+}
+
+/// `away` is whether the focus session was paused because the user is away
+fn idle_change(
+    away: bool,
+    running: bool,
+    is_focus: bool,
+    idle: Duration,
+    threshold: Duration,
+) -> IdleChange {
+    match (away, running) {
+        (false, true) if is_focus && idle >= threshold => IdleChange::Away,
+        (true, true) => IdleChange::Resumed,
+        (true, false) if idle < POLL_INTERVAL * 2 => IdleChange::Back,
+        _ => IdleChange::None,
+    }
+}
+
+/// Pauses a running focus session once the user has been idle for the configured time
+pub fn use_idle_detection() {
+    use_future(|| async {
+        let source = platform::IdleSource::new().await;
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        // set while a focus session is paused because the user is away
+        let mut away_since: Option<Instant> = None;
+
+        loop {
+            interval.tick().await;
+
+            let (enabled, threshold) = {
+                let settings = SETTINGS.peek();
+                (
+                    settings.idle_pause,
+                    Duration::from_secs(settings.idle_minutes as u64 * 60),
+                )
+            };
+            if !enabled {
+                away_since = None;
+                continue;
+            }
+            let Some(idle) = source.idle_time().await else {
+                continue;
+            };
+
+            let is_focus = SETTINGS.peek().phase(*PHASE_INDEX.peek()).is_focus();
+            let running = *TIMER_RUNNING.peek();
+            match idle_change(away_since.is_some(), running, is_focus, idle, threshold) {
+                IdleChange::Away => {
+                    pause_for_idle(idle);
+                    away_since = Some(
+                        Instant::now()
+                            .checked_sub(idle)
+                            .unwrap_or_else(Instant::now),
+                    );
+                }
+                IdleChange::Back => {
+                    if let Some(since) = away_since.take() {
+                        *AWAY_PROMPT.write() = Some(since.elapsed());
+                        send_command(WindowCommand::Show);
+                    }
+                }
+                IdleChange::Resumed => away_since = None,
+                IdleChange::None => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_secs(5 * 60);
+
+    #[test]
+    fn a_running_focus_session_pauses_at_the_threshold() {
+        let just_under = THRESHOLD - Duration::from_secs(1);
+        assert_eq!(
+            idle_change(false, true, true, just_under, THRESHOLD),
+            IdleChange::None
+        );
+        assert_eq!(
+            idle_change(false, true, true, THRESHOLD, THRESHOLD),
+            IdleChange::Away
+        );
+
+        // breaks and paused sessions are left alone
+        assert_eq!(
+            idle_change(false, true, false, THRESHOLD, THRESHOLD),
+            IdleChange::None
+        );
+        assert_eq!(
+            idle_change(false, false, true, THRESHOLD, THRESHOLD),
+            IdleChange::None
+        );
+    }
+
+    #[test]
+    fn the_user_is_back_once_active_again() {
+        assert_eq!(
+            idle_change(true, false, true, THRESHOLD, THRESHOLD),
+            IdleChange::None
+        );
+        assert_eq!(
+            idle_change(true, false, true, POLL_INTERVAL, THRESHOLD),
+            IdleChange::Back
+        );
+        assert_eq!(
+            idle_change(true, true, true, THRESHOLD, THRESHOLD),
+            IdleChange::Resumed
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    mod backends {
+        use futures_util::StreamExt;
+        use tokio::net::UnixStream;
+        use zbus::{connection::Builder, message::Type, Connection, Guid, MessageStream};
+
+        use super::super::platform::IdleSource;
+        use super::*;
+
+        /// Answers idle time requests on the other end of a peer-to-peer connection,
+        /// GNOME's in milliseconds and the screensaver's in seconds if given
+        async fn serve_idle_time(
+            connection: Connection,
+            mutter: Option<u64>,
+            screen_saver: Option<u32>,
+        ) {
+            let mut messages = MessageStream::from(&connection);
+            while let Some(Ok(message)) = messages.next().await {
+                let header = message.header();
+                if header.message_type() != Type::MethodCall {
+                    continue;
+                }
+                let member = header.member().map(ToString::to_string).unwrap_or_default();
+
+                let result = match (member.as_str(), mutter, screen_saver) {
+                    ("GetIdletime", Some(millis), _) => connection.reply(&message, &millis).await,
+                    ("GetSessionIdleTime", _, Some(secs)) => {
+                        connection.reply(&message, &secs).await
+                    }
+                    _ => {
+                        connection
+                            .reply_error(&message, "org.freedesktop.DBus.Error.UnknownMethod", &())
+                            .await
+                    }
+                };
+                result.unwrap();
+            }
+        }
+
+        async fn source(mutter: Option<u64>, screen_saver: Option<u32>) -> IdleSource {
+            let (client, server) = UnixStream::pair().unwrap();
+            let (client, server) = tokio::try_join!(
+                Builder::unix_stream(client).p2p().build(),
+                Builder::unix_stream(server)
+                    .server(Guid::generate())
+                    .unwrap()
+                    .p2p()
+                    .build(),
+            )
+            .unwrap();
+            tokio::spawn(serve_idle_time(server, mutter, screen_saver));
+
+            IdleSource {
+                session: Some(client),
+                system: None,
+            }
+        }
+
+        #[tokio::test]
+        async fn gnome_is_asked_first() {
+            let source = source(Some(1500), Some(60)).await;
+            assert_eq!(source.idle_time().await, Some(Duration::from_millis(1500)));
+        }
+
+        #[tokio::test]
+        async fn the_screensaver_is_asked_without_gnome() {
+            let source = source(None, Some(60)).await;
+            assert_eq!(source.idle_time().await, Some(Duration::from_secs(60)));
+        }
+
+        #[tokio::test]
+        async fn unknown_without_any_backend() {
+            let source = source(None, None).await;
+            assert_eq!(source.idle_time().await, None);
+        }
+    }
+}
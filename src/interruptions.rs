@@ -0,0 +1,73 @@
+use chrono::{DateTime, Local};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{components::timer::TIMER_RUNNING, settings::SETTINGS, state::PHASE_INDEX};
+
+/// Interruptions of the current focus session, most recent last
+pub static INTERRUPTIONS: GlobalSignal<Vec<Interruption>> = GlobalSignal::new(Vec::new);
+/// Set while asking what kind of interruption to log
+pub static INTERRUPTION_PROMPT: GlobalSignal<bool> = GlobalSignal::new(|| false);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterruptionKind {
+    /// Came from the user, e.g. the urge to check mail
+    Internal,
+    /// Came from someone else, e.g. a call
+    External,
+}
+
+impl InterruptionKind {
+    pub const ALL: [InterruptionKind; 2] = [InterruptionKind::Internal, InterruptionKind::External];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InterruptionKind::Internal => "Internal",
+            InterruptionKind::External => "External",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Interruption {
+    pub at: DateTime<Local>,
+    pub kind: InterruptionKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Whether there's a running focus session to log interruptions for
+pub fn can_log_interruption() -> bool {
+    *TIMER_RUNNING.read() && SETTINGS.read().phase(*PHASE_INDEX.read()).is_focus()
+}
+
+fn focus_running() -> bool {
+    *TIMER_RUNNING.peek() && SETTINGS.peek().phase(*PHASE_INDEX.peek()).is_focus()
+}
+
+/// Adds an interruption to the running focus session, ignored when no focus session is running
+pub fn log_interruption(kind: InterruptionKind, note: &str) {
+    *INTERRUPTION_PROMPT.write() = false;
+    if !focus_running() {
+        return;
+    }
+
+    let note = note.trim();
+    INTERRUPTIONS.write().push(Interruption {
+        at: Local::now(),
+        kind,
+        note: (!note.is_empty()).then(|| note.to_string()),
+    });
+}
+
+/// Asks for the kind of interruption and a note, e.g. from the keyboard shortcut
+pub fn open_interruption_prompt() {
+    if focus_running() {
+        *INTERRUPTION_PROMPT.write() = true;
+    }
+}
+
+pub fn close_interruption_prompt() {
+    *INTERRUPTION_PROMPT.write() = false;
+}
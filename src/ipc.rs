@@ -0,0 +1,153 @@
+use crate::bus::{Command, SettingsCommand, TimerCommand, WindowCommand};
+
+pub use socket::{send_to_app, use_ipc_listener};
+
+/// Commands other processes can send, one per line
+pub const COMMANDS: &str =
+    "start, toggle, skip, dismiss, snooze, undo, redo, interrupt, show, settings, preset NAME";
+
+/// Turns a line like "toggle" or "preset Deep work" into a command for the bus
+#[cfg_attr(not(unix), allow(dead_code))]
+pub fn parse_command(line: &str) -> Option<Command> {
+    let line = line.trim();
+    let (name, argument) = line.split_once(' ').unwrap_or((line, ""));
+    let argument = argument.trim();
+
+    let command = match (name, argument) {
+        ("start", "") => TimerCommand::Start.into(),
+        ("toggle", "") => TimerCommand::Toggle.into(),
+        ("skip", "") => TimerCommand::Skip.into(),
+        ("dismiss", "") => TimerCommand::Dismiss.into(),
+        ("snooze", "") => TimerCommand::Snooze.into(),
+        ("undo", "") => TimerCommand::Undo.into(),
+        ("redo", "") => TimerCommand::Redo.into(),
+        ("interrupt", "") => TimerCommand::Interrupt(None).into(),
+        ("show", "") => WindowCommand::Show.into(),
+        ("settings", "") => WindowCommand::ShowSettings.into(),
+        ("preset", name) if !name.is_empty() => {
+            SettingsCommand::SwitchPreset(name.to_string()).into()
+        }
+        _ => return None,
+    };
+    Some(command)
+}
+
+// commands come in over a Unix socket in the data directory
+#[cfg(unix)]
+mod socket {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        os::unix::net::UnixStream,
+        path::PathBuf,
+    };
+
+    use dioxus::prelude::*;
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt},
+        net::UnixListener,
+    };
+    use tracing::error;
+
+    use super::{parse_command, COMMANDS};
+    use crate::bus::send_command;
+
+    fn socket_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("AuraTimer").join("command.sock"))
+    }
+
+    /// Sends a command line to the running app and returns its reply, used by `AuraTimer send …`
+    pub fn send_to_app(line: &str) -> Result<String, String> {
+        let path = socket_path().ok_or("No data directory available")?;
+        let mut stream = UnixStream::connect(&path)
+            .map_err(|_| "AuraTimer doesn't seem to be running".to_string())?;
+
+        writeln!(stream, "{}", line.trim()).map_err(|e| e.to_string())?;
+        let mut reply = String::new();
+        BufReader::new(stream)
+            .read_line(&mut reply)
+            .map_err(|e| e.to_string())?;
+        Ok(reply.trim().to_string())
+    }
+
+    /// Applies commands sent over a local socket, e.g. from scripts. Used once in the main window.
+    pub fn use_ipc_listener() {
+        use_future(|| async {
+            let Some(path) = socket_path() else {
+                return;
+            };
+            // a socket that still answers belongs to another instance
+            if UnixStream::connect(&path).is_ok() {
+                error!("Another instance is listening on {}", path.display());
+                return;
+            }
+            let _ = std::fs::remove_file(&path);
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+
+            let listener = match UnixListener::bind(&path) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    error!("Failed to listen on {}: {}", path.display(), e);
+                    return;
+                }
+            };
+
+            while let Ok((stream, _)) = listener.accept().await {
+                spawn(async move {
+                    let (reader, mut writer) = stream.into_split();
+                    let mut lines = tokio::io::BufReader::new(reader).lines();
+
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let reply = match parse_command(&line) {
+                            Some(command) => {
+                                send_command(command);
+                                "ok".to_string()
+                            }
+                            None => {
+                                format!("unknown command \"{}\", use {}", line.trim(), COMMANDS)
+                            }
+                        };
+                        if writer
+                            .write_all(format!("{}\n", reply).as_bytes())
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+    }
+}
+
+// there is no socket to listen on elsewhere, `AuraTimer send` says so instead of failing to build
+#[cfg(not(unix))]
+mod socket {
+    pub fn send_to_app(_line: &str) -> Result<String, String> {
+        Err("Sending commands to AuraTimer isn't supported on this platform".to_string())
+    }
+
+    pub fn use_ipc_listener() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_and_arguments() {
+        assert!(matches!(
+            parse_command(" toggle\n"),
+            Some(Command::Timer(TimerCommand::Toggle))
+        ));
+        assert!(matches!(
+            parse_command("preset Deep work"),
+            Some(Command::Settings(SettingsCommand::SwitchPreset(name))) if name == "Deep work"
+        ));
+        assert!(parse_command("preset").is_none());
+        assert!(parse_command("toggle now").is_none());
+        assert!(parse_command("").is_none());
+    }
+}
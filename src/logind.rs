@@ -0,0 +1,33 @@
+use zbus::{proxy, zvariant::OwnedObjectPath};
+
+#[proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+pub trait LoginManager {
+    fn get_session(&self, session_id: &str) -> zbus::Result<OwnedObjectPath>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+pub trait LoginSession {
+    #[zbus(property)]
+    fn id(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn idle_hint(&self) -> zbus::Result<bool>;
+
+    /// Microseconds since the epoch
+    #[zbus(property)]
+    fn idle_since_hint(&self) -> zbus::Result<u64>;
+
+    #[zbus(signal)]
+    fn lock(&self) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn unlock(&self) -> zbus::Result<()>;
+}
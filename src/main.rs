@@ -1,56 +1,97 @@
 mod sound;
 
-use std::{sync::mpsc::channel, time::Duration};
-
 use dioxus::{
-    desktop::{
-        tao::platform::macos::WindowExtMacOS, window, Config, LogicalSize, WindowBuilder,
-        WindowCloseBehaviour,
-    },
+    desktop::{Config, LogicalSize, WindowBuilder, WindowCloseBehaviour},
     prelude::*,
 };
-use objc2::msg_send;
-use tracing::error;
 use tracing_subscriber::FmtSubscriber;
-use tray_icon::TrayIconEvent;
 
 use crate::{
+    bus::{use_command_handler, use_timer_state_publisher},
     components::{
-        alert::{
-            handle_timer_commands, init_timer_event_listener, TIMER_COMMAND_RECEIVER,
-            TIMER_COMMAND_SENDER, TIMER_EVENT_RECEIVER, TIMER_EVENT_SENDER,
-        },
+        away_prompt::AwayPrompt,
+        break_overlay::use_break_enforcement,
         control_buttons::ControlButtons,
         info::Info,
+        interruption_prompt::InterruptionPrompt,
+        reminder_list::{toggle_reminders, ReminderPanel},
+        settings_panel::{open_settings, SettingsPanel},
+        shortcuts_overlay::{toggle_shortcuts, ShortcutsOverlay},
+        stats_overlay::{toggle_stats, StatsOverlay},
+        task_label::use_tray_tooltip,
+        task_list::{toggle_task_list, TaskListPanel},
         timer::Timer,
         timer_expired::TimerExpired,
+        work_start_prompt::WorkStartPrompt,
     },
+    event_hooks::use_event_hooks,
+    goals::use_goal_notification,
+    history::use_history_watcher,
+    hotkeys::register_global_hotkeys,
+    idle::{use_idle_detection, AWAY_PROMPT},
+    interruptions::INTERRUPTION_PROMPT,
+    ipc::use_ipc_listener,
+    reminders::use_reminders,
+    schedule::{use_day_start, use_working_hours, WORK_START_PROMPT},
+    shortcuts::{handle_keydown, use_focus_tracking},
     state::{
-        init_colors, BG_COLOR, IS_FOCUS_MODE, IS_FOCUS_MODE_MUTEX, SMALL_SESSION_COUNT,
-        SMALL_SESSION_COUNT_MUTEX, TEXT_COLOR, TIMER_EXPIRED,
+        init_colors, BG_COLOR, SHOW_REMINDERS, SHOW_SETTINGS, SHOW_SHORTCUTS, SHOW_STATS,
+        SHOW_TASKS, TEXT_COLOR, TIMER_EXPIRED,
     },
-    tray::{
-        handle_window_commands, init_tray, init_tray_handler, init_tray_listener,
-        TRAY_EVENT_RECEIVER, TRAY_EVENT_SENDER, WINDOW_COMMAND_RECEIVER, WINDOW_COMMAND_SENDER,
-    },
-    window::{set_transparent_titlebar, WindowDragArea},
+    task_source::use_task_source_watcher,
+    tray::{init_tray, init_tray_handler, use_tray_menu},
+    ui::{icon_button::IconButton, icons::IconType},
+    webhooks::use_webhooks,
+    window::WindowDragArea,
 };
 
+mod bus;
+mod cli;
 mod components;
+mod cycle;
+mod event_hooks;
+mod goals;
+mod history;
+mod hotkeys;
+mod idle;
+mod interruptions;
+mod ipc;
+#[cfg(target_os = "linux")]
+mod logind;
+#[cfg(target_os = "linux")]
+mod media;
+mod reminders;
+mod schedule;
+#[cfg(target_os = "linux")]
+mod screen_lock;
+mod settings;
+mod shortcuts;
 mod state;
+mod storage;
+mod task_source;
+mod tasks;
+mod timer_model;
 mod tray;
 mod ui;
+mod undo;
+mod webhooks;
 mod window;
 
 fn main() {
     FmtSubscriber::builder().init();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     let config = Config::new()
         .with_window(
             WindowBuilder::new()
                 .with_inner_size(LogicalSize::new(900.0, 600.0))
                 .with_min_inner_size(LogicalSize::new(400.0, 300.0)),
         )
+        .with_menu(None)
         .with_close_behaviour(WindowCloseBehaviour::LastWindowHides);
 
     dioxus::LaunchBuilder::desktop()
@@ -63,27 +104,36 @@ fn App() -> Element {
     init_colors();
 
     use_hook(|| {
-        init_channels();
+        #[cfg(target_os = "macos")]
+        window::init_app_menu();
         init_tray();
         init_tray_handler();
-        init_tray_listener();
-        setup_window();
-        init_timer_event_listener();
+        #[cfg(target_os = "macos")]
+        window::set_transparent_titlebar();
+        register_global_hotkeys();
     });
 
-    use_future(move || async move {
-        let mut interval = tokio::time::interval(Duration::from_millis(200));
-        loop {
-            interval.tick().await;
-            handle_window_commands();
-            handle_timer_commands();
-        }
-    });
-
-    use_effect(|| {
-        *IS_FOCUS_MODE_MUTEX.lock().unwrap() = *IS_FOCUS_MODE.read();
-        *SMALL_SESSION_COUNT_MUTEX.lock().unwrap() = *SMALL_SESSION_COUNT.read();
-    });
+    use_command_handler();
+
+    use_timer_state_publisher();
+    use_tray_tooltip();
+    use_tray_menu();
+    use_break_enforcement();
+    use_task_source_watcher();
+    use_event_hooks();
+    use_webhooks();
+    use_idle_detection();
+    use_reminders();
+    use_focus_tracking();
+    use_ipc_listener();
+    use_history_watcher();
+    use_working_hours();
+    use_day_start();
+    use_goal_notification();
+    #[cfg(target_os = "linux")]
+    media::use_media_control();
+    #[cfg(target_os = "linux")]
+    screen_lock::use_screen_lock_breaks();
 
     let bg_color = *BG_COLOR.read();
     let text_color = *TEXT_COLOR.read();
@@ -91,9 +141,15 @@ fn App() -> Element {
     rsx! {
         document::Link { rel: "stylesheet", href: asset!("/assets/tailwind.css") }
         div {
-            class: format!("w-screen h-screen select-none flex flex-col {} {}", bg_color, text_color),
+            class: format!("w-screen h-screen select-none flex flex-col outline-none {} {}", bg_color, text_color),
+            // focusable so that keyboard shortcuts work without clicking into the window first
+            tabindex: "0",
+            autofocus: true,
+            onkeydown: handle_keydown,
             WindowDragArea {}
-            if *TIMER_EXPIRED.read() {
+            if *SHOW_SETTINGS.read() {
+                SettingsPanel {}
+            } else if *TIMER_EXPIRED.read() {
                 div {
                     class: "flex-grow flex items-center justify-center",
                     TimerExpired {}
@@ -108,40 +164,59 @@ fn App() -> Element {
                     }
                 }
                 ControlButtons {}
+                div {
+                    class: "absolute top-0 right-0 p-2 flex",
+                    IconButton {
+                        icon_type: IconType::List,
+                        title: "Tasks",
+                        size: "3rem",
+                        action: toggle_task_list,
+                    }
+                    IconButton {
+                        icon_type: IconType::Alarm,
+                        title: "Reminders",
+                        size: "3rem",
+                        action: toggle_reminders,
+                    }
+                    IconButton {
+                        icon_type: IconType::Chart,
+                        title: "Statistics",
+                        size: "3rem",
+                        action: toggle_stats,
+                    }
+                    IconButton {
+                        icon_type: IconType::Keyboard,
+                        title: "Keyboard shortcuts",
+                        size: "3rem",
+                        action: toggle_shortcuts,
+                    }
+                    IconButton {
+                        icon_type: IconType::Settings,
+                        title: "Settings",
+                        size: "3rem",
+                        action: open_settings,
+                    }
+                }
+            }
+            if *SHOW_TASKS.read() {
+                TaskListPanel {}
+            }
+            if *SHOW_REMINDERS.read() {
+                ReminderPanel {}
+            }
+            if *SHOW_STATS.read() {
+                StatsOverlay {}
+            }
+            if *SHOW_SHORTCUTS.read() {
+                ShortcutsOverlay {}
+            }
+            if AWAY_PROMPT.read().is_some() {
+                AwayPrompt {}
+            } else if *WORK_START_PROMPT.read() {
+                WorkStartPrompt {}
+            } else if *INTERRUPTION_PROMPT.read() {
+                InterruptionPrompt {}
             }
         }
     }
 }
-
-fn init_channels() {
-    // Tray channels
-    let (tray_tx, tray_rx) = channel::<TrayIconEvent>();
-    *TRAY_EVENT_SENDER.lock().unwrap() = Some(tray_tx);
-    *TRAY_EVENT_RECEIVER.lock().unwrap() = Some(tray_rx);
-
-    // Window command channels
-    let (window_tx, window_rx) = channel();
-    *WINDOW_COMMAND_SENDER.lock().unwrap() = Some(window_tx);
-    *WINDOW_COMMAND_RECEIVER.lock().unwrap() = Some(window_rx);
-
-    // Timer channels
-    let (timer_command_tx, timer_command_rx) = channel();
-    *TIMER_COMMAND_SENDER.lock().unwrap() = Some(timer_command_tx);
-    *TIMER_COMMAND_RECEIVER.lock().unwrap() = Some(timer_command_rx);
-
-    let (timer_event_tx, timer_event_rx) = channel();
-    *TIMER_EVENT_SENDER.lock().unwrap() = Some(timer_event_tx);
-    *TIMER_EVENT_RECEIVER.lock().unwrap() = Some(timer_event_rx);
-}
-
-fn setup_window() {
-    let ns_view: *mut objc2::runtime::AnyObject = window().ns_view().cast();
-    unsafe {
-        let ns_window: *mut objc2::runtime::AnyObject = msg_send![ns_view, window];
-        if ns_window.is_null() {
-            error!("ns_window is null, unable to set transparent titlebar");
-            return;
-        }
-        set_transparent_titlebar(ns_window);
-    }
-}
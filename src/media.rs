@@ -0,0 +1,290 @@
+use dioxus::prelude::*;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info};
+use zbus::{fdo::DBusProxy, names::OwnedBusName, proxy, proxy::CacheProperties, Connection};
+
+use crate::{
+    bus::{subscribe_events, SessionEvent, SessionEventKind},
+    history::SessionKind,
+    settings::SETTINGS,
+};
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+
+#[proxy(
+    interface = "org.mpris.MediaPlayer2.Player",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+trait Player {
+    fn pause(&self) -> zbus::Result<()>;
+
+    fn play(&self) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> zbus::Result<String>;
+}
+
+/// Pauses MPRIS media players and later resumes only the ones it paused itself
+pub struct MediaController {
+    connection: Connection,
+    paused: Vec<OwnedBusName>,
+}
+
+impl MediaController {
+    /// Works on any bus, e.g. a private one with a mock player
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            connection,
+            paused: Vec::new(),
+        }
+    }
+
+    async fn player(&self, name: &OwnedBusName) -> zbus::Result<PlayerProxy<'static>> {
+        PlayerProxy::builder(&self.connection)
+            .destination(name.clone())?
+            // the status changes outside of AuraTimer, so it's always asked for
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await
+    }
+
+    async fn players(&self) -> zbus::Result<Vec<OwnedBusName>> {
+        let names = DBusProxy::new(&self.connection).await?.list_names().await?;
+        Ok(names
+            .into_iter()
+            .filter(|name| name.starts_with(MPRIS_PREFIX))
+            .collect())
+    }
+
+    /// Pauses every player that is currently playing
+    pub async fn pause_playing(&mut self) -> zbus::Result<()> {
+        for name in self.players().await? {
+            let result = async {
+                let player = self.player(&name).await?;
+                if player.playback_status().await? == "Playing" {
+                    player.pause().await?;
+                    return Ok(true);
+                }
+                Ok::<_, zbus::Error>(false)
+            }
+            .await;
+
+            match result {
+                Ok(true) => {
+                    info!("Paused {}", name);
+                    if !self.paused.contains(&name) {
+                        self.paused.push(name);
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => error!("Failed to pause {}: {}", name, e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Resumes the players paused by `pause_playing` that are still paused
+    pub async fn resume_paused(&mut self) {
+        for name in std::mem::take(&mut self.paused) {
+            let result = async {
+                let player = self.player(&name).await?;
+                // stopped or started again by the user in the meantime
+                if player.playback_status().await? == "Paused" {
+                    player.play().await?;
+                }
+                Ok::<_, zbus::Error>(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => info!("Resumed {}", name),
+                Err(e) => error!("Failed to resume {}: {}", name, e),
+            }
+        }
+    }
+}
+
+/// Whether media should be paused after the event according to the settings
+fn wants_media_paused(event: &SessionEvent) -> bool {
+    match event.kind {
+        SessionEventKind::FocusStart | SessionEventKind::BreakStart | SessionEventKind::Resume => {
+            let settings = SETTINGS.peek();
+            match event.phase {
+                SessionKind::Focus => settings.pause_media_during_focus,
+                SessionKind::ShortBreak | SessionKind::LongBreak => {
+                    settings.pause_media_during_breaks
+                }
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Pauses media players while a focus session or break runs, depending on the settings
+pub fn use_media_control() {
+    use_future(|| async {
+        let connection = match Connection::session().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                error!(
+                    "Failed to connect to the session bus, media won't be paused: {}",
+                    e
+                );
+                return;
+            }
+        };
+        let mut controller = MediaController::new(connection);
+        let mut events = subscribe_events();
+
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if wants_media_paused(&event) {
+                        if let Err(e) = controller.pause_playing().await {
+                            error!("Failed to pause media players: {}", e);
+                        }
+                    } else {
+                        controller.resume_paused().await;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    error!("Skipped {} session events for media control", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
+
+    use futures_util::StreamExt;
+    use tokio::net::UnixStream;
+    use zbus::{connection::Builder, message::Type, zvariant::Value, Guid, MessageStream};
+
+    use super::*;
+
+    type Players = Arc<Mutex<BTreeMap<String, &'static str>>>;
+
+    /// Answers for every fake player on the other end of a peer-to-peer connection,
+    /// telling them apart by the destination of each call like the bus daemon would
+    async fn serve_players(connection: Connection, players: Players) {
+        let mut messages = MessageStream::from(&connection);
+        while let Some(Ok(message)) = messages.next().await {
+            let header = message.header();
+            if header.message_type() != Type::MethodCall {
+                continue;
+            }
+            let destination = header
+                .destination()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            let member = header.member().map(ToString::to_string).unwrap_or_default();
+
+            let result = match member.as_str() {
+                "ListNames" => {
+                    let mut names: Vec<String> = players.lock().unwrap().keys().cloned().collect();
+                    names.push("org.freedesktop.Notifications".to_string());
+                    connection.reply(&message, &names).await
+                }
+                "Get" => {
+                    let status = players.lock().unwrap()[&destination];
+                    connection.reply(&message, &Value::from(status)).await
+                }
+                "Pause" | "Play" => {
+                    let status = if member == "Pause" {
+                        "Paused"
+                    } else {
+                        "Playing"
+                    };
+                    players.lock().unwrap().insert(destination, status);
+                    connection.reply(&message, &()).await
+                }
+                _ => {
+                    connection
+                        .reply_error(&message, "org.freedesktop.DBus.Error.UnknownMethod", &())
+                        .await
+                }
+            };
+            result.unwrap();
+        }
+    }
+
+    async fn controller_with(players: &[(&str, &'static str)]) -> (MediaController, Players) {
+        let players: Players = Arc::new(Mutex::new(
+            players
+                .iter()
+                .map(|(name, status)| (format!("{}{}", MPRIS_PREFIX, name), *status))
+                .collect(),
+        ));
+
+        let (client, server) = UnixStream::pair().unwrap();
+        let (client, server) = tokio::try_join!(
+            Builder::unix_stream(client).p2p().build(),
+            Builder::unix_stream(server)
+                .server(Guid::generate())
+                .unwrap()
+                .p2p()
+                .build(),
+        )
+        .unwrap();
+        tokio::spawn(serve_players(server, players.clone()));
+
+        (MediaController::new(client), players)
+    }
+
+    fn status(players: &Players, name: &str) -> &'static str {
+        players.lock().unwrap()[&format!("{}{}", MPRIS_PREFIX, name)]
+    }
+
+    #[tokio::test]
+    async fn pauses_only_playing_players() {
+        let (mut controller, players) = controller_with(&[
+            ("vlc", "Playing"),
+            ("mpv", "Paused"),
+            ("spotify", "Stopped"),
+        ])
+        .await;
+
+        controller.pause_playing().await.unwrap();
+
+        assert_eq!(status(&players, "vlc"), "Paused");
+        assert_eq!(status(&players, "mpv"), "Paused");
+        assert_eq!(status(&players, "spotify"), "Stopped");
+    }
+
+    #[tokio::test]
+    async fn resumes_only_players_it_paused() {
+        let (mut controller, players) = controller_with(&[
+            ("vlc", "Playing"),
+            ("mpv", "Paused"),
+            ("spotify", "Playing"),
+        ])
+        .await;
+
+        controller.pause_playing().await.unwrap();
+        // stopped by the user during the session
+        players
+            .lock()
+            .unwrap()
+            .insert(format!("{}spotify", MPRIS_PREFIX), "Stopped");
+        controller.resume_paused().await;
+
+        assert_eq!(status(&players, "vlc"), "Playing");
+        assert_eq!(status(&players, "mpv"), "Paused");
+        assert_eq!(status(&players, "spotify"), "Stopped");
+
+        // nothing is left to resume
+        players
+            .lock()
+            .unwrap()
+            .insert(format!("{}vlc", MPRIS_PREFIX), "Paused");
+        controller.resume_paused().await;
+        assert_eq!(status(&players, "vlc"), "Paused");
+    }
+}
@@ -0,0 +1,308 @@
+use std::{fs, path::PathBuf, sync::LazyLock, time::Duration};
+
+use chrono::{DateTime, Local, NaiveTime, TimeDelta};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tracing::error;
+
+use crate::{
+    bus::ReminderCommand,
+    components::{
+        reminder_alert::{ReminderAlert, ReminderAlertProps},
+        timer::show_alert_window,
+    },
+    sound::play_alarm,
+    storage::write_json,
+};
+
+pub static REMINDERS: GlobalSignal<Vec<Reminder>> = GlobalSignal::new(load_reminders);
+
+// ids of the reminders that went off and weren't dismissed yet, so their alert windows can close themselves
+static RINGING: LazyLock<watch::Sender<Vec<u64>>> =
+    LazyLock::new(|| watch::Sender::new(Vec::new()));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReminderKind {
+    /// "Remind me in 12 minutes"
+    Countdown,
+    /// "At 15:30"
+    Alarm,
+}
+
+/// A one-off countdown or alarm, independent of the session sequence
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: u64,
+    pub label: String,
+    pub kind: ReminderKind,
+    pub due: DateTime<Local>,
+    /// Went off and is kept until it's dismissed
+    #[serde(default)]
+    pub fired: bool,
+}
+
+fn reminders_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("AuraTimer").join("reminders.json"))
+}
+
+fn load_reminders() -> Vec<Reminder> {
+    let Some(path) = reminders_path() else {
+        return Vec::new();
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => parse_reminders(&contents),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn parse_reminders(contents: &str) -> Vec<Reminder> {
+    serde_json::from_str(contents).unwrap_or_else(|e| {
+        error!("Failed to parse reminders: {}", e);
+        Vec::new()
+    })
+}
+
+fn save_reminders(reminders: &[Reminder]) -> Result<(), Box<dyn std::error::Error>> {
+    let path = reminders_path().ok_or("No data directory available")?;
+    write_json(&path, &reminders)
+}
+
+fn update_reminders(update: impl FnOnce(&mut Vec<Reminder>)) {
+    update(&mut REMINDERS.write());
+
+    if let Err(e) = save_reminders(&REMINDERS.peek()) {
+        error!("Failed to save reminders: {}", e);
+    }
+}
+
+fn add_reminder(label: &str, kind: ReminderKind, due: DateTime<Local>) {
+    let label = match label.trim() {
+        "" => "Reminder",
+        label => label,
+    };
+
+    update_reminders(|reminders| {
+        let id = reminders
+            .iter()
+            .map(|reminder| reminder.id)
+            .max()
+            .map_or(0, |id| id + 1);
+        reminders.push(Reminder {
+            id,
+            label: label.to_string(),
+            kind,
+            due,
+            fired: false,
+        });
+        reminders.sort_by_key(|reminder| reminder.due);
+    });
+}
+
+/// Goes off after `minutes`
+pub fn add_countdown(label: &str, minutes: u32) -> Result<(), String> {
+    if !(1..=24 * 60).contains(&minutes) {
+        return Err("Countdowns must be between 1 minute and 24 hours".to_string());
+    }
+
+    add_reminder(
+        label,
+        ReminderKind::Countdown,
+        Local::now() + TimeDelta::minutes(minutes as i64),
+    );
+    Ok(())
+}
+
+/// Goes off at the next time the clock shows `time`, e.g. "15:30"
+pub fn add_alarm(label: &str, time: &str) -> Result<(), String> {
+    let time = NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| format!("\"{}\" isn't a time like 15:30", time.trim()))?;
+
+    add_reminder(label, ReminderKind::Alarm, alarm_due(time, Local::now())?);
+    Ok(())
+}
+
+/// The next time after `now` that the clock shows `time`
+fn alarm_due(time: NaiveTime, now: DateTime<Local>) -> Result<DateTime<Local>, String> {
+    let mut date = now.date_naive();
+    if time <= now.time() {
+        date = date.succ_opt().ok_or("Invalid date")?;
+    }
+    // skipped by a daylight saving change
+    date.and_time(time)
+        .and_local_timezone(Local)
+        .earliest()
+        .ok_or(format!(
+            "{} doesn't exist on that day",
+            time.format("%H:%M")
+        ))
+}
+
+/// Removes a reminder, whether it's still pending or ringing
+pub fn remove_reminder(id: u64) {
+    update_reminders(|reminders| reminders.retain(|reminder| reminder.id != id));
+}
+
+pub fn handle_reminder_command(command: ReminderCommand) {
+    match command {
+        ReminderCommand::Dismiss(id) => remove_reminder(id),
+    }
+}
+
+/// Label and due time of pending reminders, e.g. for the tray menu
+pub fn pending_reminders() -> Vec<String> {
+    REMINDERS
+        .read()
+        .iter()
+        .filter(|reminder| !reminder.fired)
+        .map(|reminder| format!("{} – {}", reminder.label, reminder.due.format("%H:%M")))
+        .collect()
+}
+
+/// Marks the reminders due at `now` as fired and returns them, including those that came due while the app was closed
+fn take_due(reminders: &mut [Reminder], now: DateTime<Local>) -> Vec<Reminder> {
+    reminders
+        .iter_mut()
+        .filter(|reminder| !reminder.fired && reminder.due <= now)
+        .map(|reminder| {
+            reminder.fired = true;
+            reminder.clone()
+        })
+        .collect()
+}
+
+fn fire_due_reminders() {
+    let mut reminders = REMINDERS.peek().clone();
+    let due = take_due(&mut reminders, Local::now());
+    if due.is_empty() {
+        return;
+    }
+
+    update_reminders(|current| *current = reminders);
+
+    play_alarm();
+    for reminder in due {
+        show_alert_window(move || {
+            VirtualDom::new_with_props(
+                ReminderAlert,
+                ReminderAlertProps {
+                    id: reminder.id,
+                    label: reminder.label.clone(),
+                },
+            )
+        });
+    }
+}
+
+/// Fires reminders when they're due and publishes the ringing ones, used once in the main window
+pub fn use_reminders() {
+    use_effect(|| {
+        let ringing: Vec<u64> = REMINDERS
+            .read()
+            .iter()
+            .filter(|reminder| reminder.fired)
+            .map(|reminder| reminder.id)
+            .collect();
+
+        RINGING.send_if_modified(|current| {
+            if *current == ringing {
+                return false;
+            }
+            *current = ringing;
+            true
+        });
+    });
+
+    use_future(|| async {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            fire_due_reminders();
+        }
+    });
+}
+
+/// Whether the reminder is still ringing, for alert windows with their own VirtualDom
+pub fn use_ringing(id: u64) -> Signal<bool> {
+    let mut ringing = use_signal(|| RINGING.borrow().contains(&id));
+
+    use_future(move || async move {
+        let mut receiver = RINGING.subscribe();
+        while receiver.changed().await.is_ok() {
+            let current = receiver.borrow_and_update().contains(&id);
+            ringing.set(current);
+        }
+    });
+
+    ringing
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2024, 5, 1, hour, minute, 0)
+            .earliest()
+            .unwrap()
+    }
+
+    fn reminder(id: u64, due: DateTime<Local>) -> Reminder {
+        Reminder {
+            id,
+            label: "Tea".to_string(),
+            kind: ReminderKind::Countdown,
+            due,
+            fired: false,
+        }
+    }
+
+    #[test]
+    fn an_alarm_goes_off_the_next_time_the_clock_shows_it() {
+        let half_past_three = NaiveTime::from_hms_opt(15, 30, 0).unwrap();
+        assert_eq!(alarm_due(half_past_three, at(14, 0)), Ok(at(15, 30)));
+
+        let tomorrow = at(15, 30) + TimeDelta::days(1);
+        assert_eq!(alarm_due(half_past_three, at(15, 30)), Ok(tomorrow));
+        assert_eq!(alarm_due(half_past_three, at(16, 0)), Ok(tomorrow));
+    }
+
+    #[test]
+    fn reminders_fire_once_when_due() {
+        let mut reminders = vec![reminder(0, at(10, 0)), reminder(1, at(11, 0))];
+
+        assert!(take_due(&mut reminders, at(9, 59)).is_empty());
+        let due = take_due(&mut reminders, at(10, 30));
+        assert_eq!(due.iter().map(|r| r.id).collect::<Vec<_>>(), [0]);
+        assert!(due[0].fired);
+        assert!(take_due(&mut reminders, at(10, 30)).is_empty());
+
+        // a reminder that came due while the app was closed fires right away
+        let due = take_due(&mut reminders, at(18, 0));
+        assert_eq!(due.iter().map(|r| r.id).collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn reminders_survive_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reminders.json");
+        let mut reminders = vec![reminder(0, at(10, 0)), reminder(1, at(11, 0))];
+        take_due(&mut reminders, at(10, 30));
+
+        write_json(&path, &reminders).unwrap();
+        let loaded = parse_reminders(&fs::read_to_string(&path).unwrap());
+        assert_eq!(loaded, reminders);
+        // the one still ringing doesn't ring again, the pending one fires when due
+        let mut loaded = loaded;
+        assert_eq!(take_due(&mut loaded, at(10, 45)), []);
+        assert_eq!(take_due(&mut loaded, at(11, 0))[0].id, 1);
+
+        // written before reminders could be kept after going off
+        let older = r#"[{"id":3,"label":"Call","kind":"Alarm","due":"2024-05-01T12:00:00+00:00"}]"#;
+        assert!(!parse_reminders(older)[0].fired);
+    }
+}
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
+use dioxus::prelude::*;
+
+use crate::{
+    bus::{send_command, WindowCommand},
+    components::timer::{session_in_progress, start_new_day, TIMER_RUNNING},
+    settings::SETTINGS,
+    sound::play_alarm,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Set when a working hours block begins while no session is running
+pub static WORK_START_PROMPT: GlobalSignal<bool> = GlobalSignal::new(|| false);
+/// Start of the current day, see `Settings::day_start`
+pub static DAY_START: GlobalSignal<DateTime<Local>> =
+    GlobalSignal::new(|| SETTINGS.peek().day_start(Local::now()));
+
+/// Whether the day starting at `current` follows the one starting at `previous`,
+/// moving the boundary within the same day isn't a new day
+fn is_new_day(previous: DateTime<Local>, current: DateTime<Local>) -> bool {
+    current.date_naive() != previous.date_naive()
+}
+
+/// Keeps `DAY_START` up to date and starts the session counts over each day if enabled,
+/// a session in progress at the boundary is finished first
+pub fn use_day_start() {
+    use_future(|| async {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        let mut reset_pending = false;
+
+        loop {
+            interval.tick().await;
+
+            let settings = SETTINGS.peek().clone();
+            let day_start = settings.day_start(Local::now());
+            let previous_day_start = *DAY_START.peek();
+            if day_start != previous_day_start {
+                *DAY_START.write() = day_start;
+                reset_pending |= is_new_day(previous_day_start, day_start);
+            }
+
+            if reset_pending && !settings.reset_count_daily {
+                reset_pending = false;
+            } else if reset_pending && !session_in_progress() {
+                start_new_day();
+                reset_pending = false;
+            }
+        }
+    });
+}
+
+/// Prompts to start focusing when a working hours block begins
+pub fn use_working_hours() {
+    use_future(|| async {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        // None until the first check, so launching the app during a block doesn't prompt
+        let mut last_block: Option<Option<(NaiveDate, NaiveTime)>> = None;
+
+        loop {
+            interval.tick().await;
+
+            let now = Local::now();
+            let settings = SETTINGS.peek().clone();
+
+            let block = settings
+                .work_block_at(now)
+                .map(|block| (now.date_naive(), block.start));
+            let block_started = last_block.is_some_and(|last| last != block) && block.is_some();
+            if block_started && !*TIMER_RUNNING.peek() && !session_in_progress() {
+                *WORK_START_PROMPT.write() = true;
+                play_alarm();
+                send_command(WindowCommand::Show);
+            } else if block.is_none() && *WORK_START_PROMPT.peek() {
+                *WORK_START_PROMPT.write() = false;
+            }
+            last_block = Some(block);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::settings::Settings;
+
+    fn at(day: u32, hour: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2024, 5, day, hour, 30, 0)
+            .earliest()
+            .unwrap()
+    }
+
+    #[test]
+    fn a_new_day_begins_at_the_day_start_hour() {
+        let settings = Settings::default();
+        let evening = settings.day_start(at(1, 22));
+
+        assert!(!is_new_day(evening, settings.day_start(at(2, 3))));
+        assert!(is_new_day(evening, settings.day_start(at(2, 5))));
+
+        // moving the boundary to a later hour of the same day doesn't count twice
+        let later = Settings {
+            day_start_hour: 6,
+            ..settings.clone()
+        };
+        let today = settings.day_start(at(2, 7));
+        assert!(!is_new_day(today, later.day_start(at(2, 7))));
+    }
+}
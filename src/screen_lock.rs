@@ -0,0 +1,185 @@
+use dioxus::prelude::*;
+use futures_util::{stream, StreamExt};
+use tracing::{error, info};
+use zbus::Connection;
+
+use crate::{
+    bus::{send_command, TimerCommand, WindowCommand},
+    components::timer::{end_lock_break, pause_timer, take_break_now, TIMER_RUNNING},
+    logind::{LoginManagerProxy, LoginSessionProxy},
+    settings::SETTINGS,
+    state::PHASE_INDEX,
+};
+
+/// Proxy for the logind session AuraTimer runs in
+///
+/// Signals are sent from the session's real path instead of `/session/auto`, so that is looked up first.
+pub async fn current_session(connection: &Connection) -> zbus::Result<LoginSessionProxy<'static>> {
+    let id = LoginSessionProxy::new(connection).await?.id().await?;
+    let path = LoginManagerProxy::new(connection)
+        .await?
+        .get_session(&id)
+        .await?;
+
+    LoginSessionProxy::builder(connection)
+        .path(path)?
+        .build()
+        .await
+}
+
+/// Streams `true` when the session is locked and `false` when it's unlocked
+pub async fn lock_changes(
+    session: &LoginSessionProxy<'static>,
+) -> zbus::Result<impl futures_util::Stream<Item = bool>> {
+    let locks = session.receive_lock().await?.map(|_| true);
+    let unlocks = session.receive_unlock().await?.map(|_| false);
+    Ok(stream::select(locks, unlocks))
+}
+
+/// Treats locking the screen as taking a break, if enabled in the settings
+pub fn use_screen_lock_breaks() {
+    use_future(|| async {
+        let result = async {
+            let connection = Connection::system().await?;
+            let session = current_session(&connection).await?;
+            lock_changes(&session).await
+        }
+        .await;
+        let mut changes = match result {
+            Ok(changes) => Box::pin(changes),
+            Err(e) => {
+                error!("Failed to watch for screen locks: {}", e);
+                return;
+            }
+        };
+        // whether the running break was started or resumed by locking the screen
+        let mut lock_break = false;
+
+        while let Some(locked) = changes.next().await {
+            if !SETTINGS.peek().break_on_lock {
+                // a focus session paused by an earlier lock isn't left waiting
+                if !locked {
+                    end_lock_break();
+                }
+                lock_break = false;
+                continue;
+            }
+
+            let is_focus_mode = SETTINGS.peek().phase(*PHASE_INDEX.peek()).is_focus();
+            let running = *TIMER_RUNNING.peek();
+            if locked {
+                info!("Screen locked");
+                if is_focus_mode {
+                    // stepping away during a focus session pauses it and the break runs meanwhile
+                    lock_break = take_break_now();
+                } else if !running {
+                    // also starts a break that is waiting after its alert
+                    send_command(TimerCommand::Toggle);
+                    lock_break = true;
+                }
+            } else {
+                info!("Screen unlocked");
+                // the focus session paused by the lock comes back, any other break only counts while
+                // the screen is locked, strict or not
+                if lock_break && !end_lock_break() && running && !is_focus_mode {
+                    pause_timer();
+                }
+                lock_break = false;
+                send_command(WindowCommand::Show);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures_util::StreamExt;
+    use tokio::net::UnixStream;
+    use zbus::{
+        connection::Builder,
+        message::Type,
+        zvariant::{ObjectPath, Value},
+        Guid, Message, MessageStream,
+    };
+
+    use super::*;
+
+    const LOGIND: &str = "org.freedesktop.login1";
+    const SESSION_INTERFACE: &str = "org.freedesktop.login1.Session";
+    const SESSION_PATH: &str = "/org/freedesktop/login1/session/_32";
+    // what the bus daemon would call the fake logind, signals are matched against it
+    const LOGIND_UNIQUE_NAME: &str = ":1.1";
+
+    /// Answers like logind with a single session "2" on the other end of a peer-to-peer connection
+    async fn serve_logind(connection: Connection) {
+        let mut messages = MessageStream::from(&connection);
+        while let Some(Ok(message)) = messages.next().await {
+            let header = message.header();
+            if header.message_type() != Type::MethodCall {
+                continue;
+            }
+            let member = header.member().map(ToString::to_string).unwrap_or_default();
+
+            let result = match member.as_str() {
+                "GetNameOwner" => connection.reply(&message, &LOGIND_UNIQUE_NAME).await,
+                "GetSession" => {
+                    let id: String = message.body().deserialize().unwrap();
+                    assert_eq!(id, "2");
+                    let path = ObjectPath::try_from(SESSION_PATH).unwrap();
+                    connection.reply(&message, &path).await
+                }
+                "Get" => connection.reply(&message, &Value::from("2")).await,
+                "GetAll" => {
+                    let properties = HashMap::from([("Id", Value::from("2"))]);
+                    connection.reply(&message, &properties).await
+                }
+                _ => {
+                    connection
+                        .reply_error(&message, "org.freedesktop.DBus.Error.UnknownMethod", &())
+                        .await
+                }
+            };
+            result.unwrap();
+        }
+    }
+
+    async fn emit(logind: &Connection, path: &str, name: &str) {
+        let signal = Message::signal(path, SESSION_INTERFACE, name)
+            .unwrap()
+            .sender(LOGIND_UNIQUE_NAME)
+            .unwrap()
+            .build(&())
+            .unwrap();
+        logind.send(&signal).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn follows_lock_and_unlock_of_the_own_session() {
+        let (client, server) = UnixStream::pair().unwrap();
+        let (client, logind) = tokio::try_join!(
+            Builder::unix_stream(client).p2p().build(),
+            Builder::unix_stream(server)
+                .server(Guid::generate())
+                .unwrap()
+                .p2p()
+                .build(),
+        )
+        .unwrap();
+        tokio::spawn(serve_logind(logind.clone()));
+
+        let session = current_session(&client).await.unwrap();
+        assert_eq!(session.inner().destination().as_str(), LOGIND);
+        assert_eq!(session.inner().path().as_str(), SESSION_PATH);
+        let mut changes = Box::pin(lock_changes(&session).await.unwrap());
+
+        emit(&logind, SESSION_PATH, "Lock").await;
+        assert_eq!(changes.next().await, Some(true));
+
+        // another user's session locking doesn't matter
+        emit(&logind, "/org/freedesktop/login1/session/_33", "Lock").await;
+        emit(&logind, SESSION_PATH, "Unlock").await;
+        assert_eq!(changes.next().await, Some(false));
+    }
+}
@@ -0,0 +1,1026 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use chrono::{DateTime, Datelike, Local, NaiveTime, TimeDelta, Timelike};
+use dioxus::signals::{GlobalSignal, Readable};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    bus::{SessionEventKind, SettingsCommand},
+    components::timer::apply_settings,
+    history::SessionKind,
+    hotkeys::{default_global_hotkeys, register_global_hotkeys, GlobalHotkeyAction},
+    shortcuts::{default_shortcuts, KeyCombo, ShortcutAction},
+    storage::write_json,
+};
+
+pub static SETTINGS: GlobalSignal<Settings> = GlobalSignal::new(Settings::load);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    Classic,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Classic, Theme::Dark];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Classic => "Classic",
+            Theme::Dark => "Dark",
+        }
+    }
+}
+
+/// Color scheme of a phase, shown while it runs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PhaseColor {
+    #[default]
+    Blue,
+    Green,
+    Teal,
+    Purple,
+    Pink,
+    Orange,
+    Amber,
+}
+
+/// Tailwind classes for the parts of the window that follow the phase color
+pub struct PhaseClasses {
+    pub bg: &'static str,
+    pub bg_hover: &'static str,
+    pub bg_inverted: &'static str,
+    pub text: &'static str,
+    pub text_inverted: &'static str,
+    pub icon: &'static str,
+}
+
+impl PhaseColor {
+    pub const ALL: [PhaseColor; 7] = [
+        PhaseColor::Blue,
+        PhaseColor::Green,
+        PhaseColor::Teal,
+        PhaseColor::Purple,
+        PhaseColor::Pink,
+        PhaseColor::Orange,
+        PhaseColor::Amber,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PhaseColor::Blue => "Blue",
+            PhaseColor::Green => "Green",
+            PhaseColor::Teal => "Teal",
+            PhaseColor::Purple => "Purple",
+            PhaseColor::Pink => "Pink",
+            PhaseColor::Orange => "Orange",
+            PhaseColor::Amber => "Amber",
+        }
+    }
+
+    // spelled out so tailwind picks up every class
+    pub fn classes(&self) -> PhaseClasses {
+        match self {
+            PhaseColor::Blue => PhaseClasses {
+                bg: "bg-blue-200",
+                bg_hover: "hover:bg-blue-500",
+                bg_inverted: "bg-blue-500",
+                text: "text-blue-500",
+                text_inverted: "text-blue-200",
+                icon: "fill-blue-500 stroke-blue-500",
+            },
+            PhaseColor::Green => PhaseClasses {
+                bg: "bg-green-200",
+                bg_hover: "hover:bg-green-500",
+                bg_inverted: "bg-green-500",
+                text: "text-green-500",
+                text_inverted: "text-green-200",
+                icon: "fill-green-500 stroke-green-500",
+            },
+            PhaseColor::Teal => PhaseClasses {
+                bg: "bg-teal-200",
+                bg_hover: "hover:bg-teal-500",
+                bg_inverted: "bg-teal-500",
+                text: "text-teal-500",
+                text_inverted: "text-teal-200",
+                icon: "fill-teal-500 stroke-teal-500",
+            },
+            PhaseColor::Purple => PhaseClasses {
+                bg: "bg-purple-200",
+                bg_hover: "hover:bg-purple-500",
+                bg_inverted: "bg-purple-500",
+                text: "text-purple-500",
+                text_inverted: "text-purple-200",
+                icon: "fill-purple-500 stroke-purple-500",
+            },
+            PhaseColor::Pink => PhaseClasses {
+                bg: "bg-pink-200",
+                bg_hover: "hover:bg-pink-500",
+                bg_inverted: "bg-pink-500",
+                text: "text-pink-500",
+                text_inverted: "text-pink-200",
+                icon: "fill-pink-500 stroke-pink-500",
+            },
+            PhaseColor::Orange => PhaseClasses {
+                bg: "bg-orange-200",
+                bg_hover: "hover:bg-orange-500",
+                bg_inverted: "bg-orange-500",
+                text: "text-orange-500",
+                text_inverted: "text-orange-200",
+                icon: "fill-orange-500 stroke-orange-500",
+            },
+            PhaseColor::Amber => PhaseClasses {
+                bg: "bg-amber-200",
+                bg_hover: "hover:bg-amber-500",
+                bg_inverted: "bg-amber-500",
+                text: "text-amber-500",
+                text_inverted: "text-amber-200",
+                icon: "fill-amber-500 stroke-amber-500",
+            },
+        }
+    }
+}
+
+/// One step of the session sequence
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Phase {
+    pub name: String,
+    pub minutes: u32,
+    /// Focus phases count as sessions and take the task label, the others behave like breaks
+    pub kind: SessionKind,
+    pub color: PhaseColor,
+}
+
+impl Default for Phase {
+    fn default() -> Self {
+        Phase::new("Focus", 25, SessionKind::Focus, PhaseColor::Blue)
+    }
+}
+
+impl Phase {
+    pub fn new(name: &str, minutes: u32, kind: SessionKind, color: PhaseColor) -> Self {
+        Self {
+            name: name.to_string(),
+            minutes,
+            kind,
+            color,
+        }
+    }
+
+    pub fn is_focus(&self) -> bool {
+        self.kind == SessionKind::Focus
+    }
+
+    /// Duration in milliseconds
+    pub fn duration(&self) -> u32 {
+        self.minutes.saturating_mul(60 * 1000)
+    }
+}
+
+/// Saved durations and sequence that can be switched to as a whole
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub focus_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    pub long_break_interval: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sequence: Vec<Phase>,
+}
+
+impl Preset {
+    fn classic(name: &str, focus: u32, short_break: u32, long_break: u32, interval: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            focus_minutes: focus,
+            short_break_minutes: short_break,
+            long_break_minutes: long_break,
+            long_break_interval: interval,
+            sequence: Vec::new(),
+        }
+    }
+
+    fn from_settings(name: &str, settings: &Settings) -> Self {
+        Self {
+            name: name.to_string(),
+            focus_minutes: settings.focus_minutes,
+            short_break_minutes: settings.short_break_minutes,
+            long_break_minutes: settings.long_break_minutes,
+            long_break_interval: settings.long_break_interval,
+            sequence: settings.sequence.clone(),
+        }
+    }
+
+    /// Whether the settings use exactly the durations and sequence of this preset
+    fn matches(&self, settings: &Settings) -> bool {
+        *self == Self::from_settings(&self.name, settings)
+    }
+}
+
+/// Preset the default durations come from
+const CLASSIC_PRESET: &str = "Classic 25/5/15";
+
+const DEFAULT_SKIP_PHRASE: &str = "I really need to skip this break";
+
+pub fn default_presets() -> Vec<Preset> {
+    vec![
+        Preset::classic(CLASSIC_PRESET, 25, 5, 15, 4),
+        Preset::classic("Deep work 50/10", 50, 10, 30, 3),
+        Preset::classic("Study 45/15", 45, 15, 30, 3),
+    ]
+}
+
+/// A stretch of working time on some weekdays
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkBlock {
+    /// Monday first
+    pub days: [bool; 7],
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl WorkBlock {
+    pub const DAY_NAMES: [&'static str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+    fn weekdays(start: (u32, u32), end: (u32, u32)) -> Self {
+        Self {
+            days: [true, true, true, true, true, false, false],
+            start: NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap_or_default(),
+            end: NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap_or_default(),
+        }
+    }
+
+    pub fn contains(&self, at: DateTime<Local>) -> bool {
+        self.days[at.weekday().num_days_from_monday() as usize]
+            && self.start <= at.time()
+            && at.time() < self.end
+    }
+}
+
+pub fn default_working_hours() -> Vec<WorkBlock> {
+    vec![
+        WorkBlock::weekdays((9, 0), (12, 30)),
+        WorkBlock::weekdays((13, 30), (17, 30)),
+    ]
+}
+
+/// Which display(s) the alert window is shown on
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AlertDisplay {
+    Cursor,
+    Primary,
+    All,
+}
+
+impl AlertDisplay {
+    pub const ALL: [AlertDisplay; 3] = [
+        AlertDisplay::Cursor,
+        AlertDisplay::Primary,
+        AlertDisplay::All,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertDisplay::Cursor => "Display under cursor",
+            AlertDisplay::Primary => "Primary display",
+            AlertDisplay::All => "All displays",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AlertPosition {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl AlertPosition {
+    pub const ALL: [AlertPosition; 3] = [
+        AlertPosition::Top,
+        AlertPosition::Center,
+        AlertPosition::Bottom,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertPosition::Top => "Top",
+            AlertPosition::Center => "Center",
+            AlertPosition::Bottom => "Bottom",
+        }
+    }
+}
+
+/// What the daily goal counts
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DailyGoal {
+    Off,
+    Sessions,
+    Minutes,
+}
+
+impl DailyGoal {
+    pub const ALL: [DailyGoal; 3] = [DailyGoal::Off, DailyGoal::Sessions, DailyGoal::Minutes];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DailyGoal::Off => "Off",
+            DailyGoal::Sessions => "Focus sessions",
+            DailyGoal::Minutes => "Focus minutes",
+        }
+    }
+}
+
+/// How a strict break can be ended early
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum EmergencySkip {
+    #[default]
+    HoldKey,
+    TypePhrase,
+}
+
+impl EmergencySkip {
+    pub const ALL: [EmergencySkip; 2] = [EmergencySkip::HoldKey, EmergencySkip::TypePhrase];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EmergencySkip::HoldKey => "Hold Esc",
+            EmergencySkip::TypePhrase => "Type a phrase",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub focus_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// Number of focus sessions before a long break
+    pub long_break_interval: u32,
+    /// Phases that run in order and start over after the last one,
+    /// empty for the classic Pomodoro cycle from the durations above
+    pub sequence: Vec<Phase>,
+    /// Focus sessions count up until they're ended by hand, the break after them is a share of the focus time
+    pub flowtime: bool,
+    pub flowtime_break_percent: u32,
+    pub presets: Vec<Preset>,
+    /// Name of the preset last switched to, it only counts as active while the durations still match it
+    pub active_preset: String,
+
+    pub play_sound: bool,
+    /// Alarm volume in percent
+    pub sound_volume: u32,
+
+    pub auto_start_breaks: bool,
+    pub auto_start_focus: bool,
+
+    /// Pause focus sessions after `idle_minutes` without keyboard or mouse input
+    pub idle_pause: bool,
+    pub idle_minutes: u32,
+    /// Don't count the time away towards the focus session
+    pub subtract_idle_time: bool,
+    /// Locking the screen pauses a running focus session and runs the break after it until it's unlocked,
+    /// then the focus session is back, paused with the time it had left. Linux only.
+    pub break_on_lock: bool,
+
+    /// Only schedule sessions during `working_hours` and prompt to start when a block begins
+    pub use_working_hours: bool,
+    pub working_hours: Vec<WorkBlock>,
+    /// Start the sequence over and count sessions and cycles from zero every day, after a session in progress ends
+    pub reset_count_daily: bool,
+    /// Hour at which a new day starts, so late nights still count towards the day before
+    pub day_start_hour: u32,
+    pub daily_goal: DailyGoal,
+    pub daily_goal_sessions: u32,
+    pub daily_goal_minutes: u32,
+
+    pub theme: Theme,
+
+    pub show_alert_window: bool,
+    pub alert_display: AlertDisplay,
+    pub alert_position: AlertPosition,
+
+    /// Cover all displays during breaks until they are over
+    pub strict_breaks: bool,
+    pub emergency_skip: EmergencySkip,
+    pub emergency_skip_phrase: String,
+    pub show_tray_countdown: bool,
+
+    pub shortcuts: BTreeMap<ShortcutAction, String>,
+    /// System-wide hotkeys, an empty binding disables the hotkey
+    pub global_hotkeys: BTreeMap<GlobalHotkeyAction, String>,
+
+    /// todo.txt or Markdown checklist to import tasks from, empty for none
+    pub task_source: String,
+
+    /// Shell commands run on session events, missing or empty for none
+    pub hooks: BTreeMap<SessionEventKind, String>,
+    /// Hooks running longer than this are killed
+    pub hook_timeout_secs: u32,
+    /// Pause MPRIS media players while sessions run, Linux only
+    pub pause_media_during_focus: bool,
+    pub pause_media_during_breaks: bool,
+
+    /// URLs that get a JSON payload for every session event, one per line as typed
+    pub webhooks: Vec<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            focus_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_interval: 4,
+            sequence: Vec::new(),
+            flowtime: false,
+            flowtime_break_percent: 20,
+            presets: default_presets(),
+            active_preset: CLASSIC_PRESET.to_string(),
+            play_sound: true,
+            sound_volume: 100,
+            auto_start_breaks: false,
+            auto_start_focus: false,
+            idle_pause: false,
+            idle_minutes: 5,
+            subtract_idle_time: true,
+            break_on_lock: false,
+            use_working_hours: false,
+            working_hours: default_working_hours(),
+            reset_count_daily: false,
+            day_start_hour: 4,
+            daily_goal: DailyGoal::Off,
+            daily_goal_sessions: 8,
+            daily_goal_minutes: 200,
+            theme: Theme::Classic,
+            show_alert_window: true,
+            alert_display: AlertDisplay::Cursor,
+            alert_position: AlertPosition::Top,
+            strict_breaks: false,
+            emergency_skip: EmergencySkip::HoldKey,
+            emergency_skip_phrase: DEFAULT_SKIP_PHRASE.to_string(),
+            show_tray_countdown: true,
+            shortcuts: default_shortcuts(),
+            global_hotkeys: default_global_hotkeys(),
+            task_source: String::new(),
+            hooks: BTreeMap::new(),
+            hook_timeout_secs: 10,
+            webhooks: Vec::new(),
+            pause_media_during_focus: false,
+            pause_media_during_breaks: false,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let Some(path) = settings_path() else {
+            return Self::default();
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents).unwrap_or_else(|e| {
+                // the defaults are saved with the next change, which mustn't lose the user's file
+                let backup = path.with_extension("json.bak");
+                error!(
+                    "{}, using defaults and keeping the file as {}",
+                    e,
+                    backup.display()
+                );
+                if let Err(e) = fs::copy(&path, &backup) {
+                    error!("Failed to back up settings: {}", e);
+                }
+                Self::default()
+            }),
+            // first launch, nothing saved yet
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Settings from the contents of a settings file, which may have been edited by hand.
+    /// Invalid entries are repaired or dropped, an error means the file can't be used at all.
+    fn parse(contents: &str) -> Result<Self, String> {
+        let settings = serde_json::from_str::<Self>(contents)
+            .map_err(|e| format!("Failed to parse settings: {}", e))?
+            .repaired();
+        settings
+            .validate()
+            .map_err(|e| format!("Invalid settings: {}", e))?;
+        Ok(settings)
+    }
+
+    /// Moves numbers out of range back into the range `validate` accepts and fixes or drops other entries it
+    /// would reject, so a single mistake in the file doesn't cost all the other settings
+    fn repaired(mut self) -> Self {
+        self.focus_minutes = self.focus_minutes.clamp(1, 180);
+        self.short_break_minutes = self.short_break_minutes.clamp(1, 60);
+        self.long_break_minutes = self.long_break_minutes.clamp(1, 120);
+        self.long_break_interval = self.long_break_interval.clamp(1, 12);
+        for phase in &mut self.sequence {
+            phase.minutes = phase.minutes.clamp(1, 240);
+        }
+        self.flowtime_break_percent = self.flowtime_break_percent.clamp(5, 100);
+        self.sound_volume = self.sound_volume.min(100);
+        self.idle_minutes = self.idle_minutes.clamp(1, 60);
+        self.hook_timeout_secs = self.hook_timeout_secs.clamp(1, 300);
+        self.day_start_hour = self.day_start_hour.min(23);
+        self.daily_goal_sessions = self.daily_goal_sessions.clamp(1, 50);
+        self.daily_goal_minutes = self.daily_goal_minutes.clamp(1, 1440);
+
+        for phase in &mut self.sequence {
+            if phase.name.trim().is_empty() {
+                phase.name = phase.kind.label().to_string();
+            }
+        }
+        self.presets.retain(|preset| !preset.name.trim().is_empty());
+        if self.emergency_skip_phrase.trim().is_empty() {
+            self.emergency_skip_phrase = DEFAULT_SKIP_PHRASE.to_string();
+        }
+        self.working_hours
+            .retain(|block| block.start < block.end && block.days.contains(&true));
+        self.webhooks
+            .retain(|url| url.trim().is_empty() || is_webhook_url(url.trim()));
+        // broken shortcuts go back to their default, broken global hotkeys are turned off
+        self.shortcuts
+            .retain(|_, binding| binding.parse::<KeyCombo>().is_ok());
+        for binding in self.global_hotkeys.values_mut() {
+            if !binding.is_empty() && !is_global_hotkey(binding) {
+                binding.clear();
+            }
+        }
+        self
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = settings_path().ok_or("No config directory available")?;
+        write_json(&path, self)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=180).contains(&self.focus_minutes) {
+            return Err("Focus duration must be between 1 and 180 minutes".to_string());
+        }
+        if !(1..=60).contains(&self.short_break_minutes) {
+            return Err("Short break must be between 1 and 60 minutes".to_string());
+        }
+        if !(1..=120).contains(&self.long_break_minutes) {
+            return Err("Long break must be between 1 and 120 minutes".to_string());
+        }
+        if !(1..=12).contains(&self.long_break_interval) {
+            return Err("Long break interval must be between 1 and 12 sessions".to_string());
+        }
+        for (i, phase) in self.sequence.iter().enumerate() {
+            if phase.name.trim().is_empty() {
+                return Err(format!("Phase {} needs a name", i + 1));
+            }
+            if !(1..=240).contains(&phase.minutes) {
+                return Err(format!(
+                    "\"{}\" must be between 1 and 240 minutes",
+                    phase.name
+                ));
+            }
+        }
+        if !(5..=100).contains(&self.flowtime_break_percent) {
+            return Err("Flowtime breaks must be between 5 and 100% of the focus time".to_string());
+        }
+        if self
+            .presets
+            .iter()
+            .any(|preset| preset.name.trim().is_empty())
+        {
+            return Err("Presets need a name".to_string());
+        }
+        if self.sound_volume > 100 {
+            return Err("Volume must be between 0 and 100".to_string());
+        }
+        if !(1..=60).contains(&self.idle_minutes) {
+            return Err("Idle time must be between 1 and 60 minutes".to_string());
+        }
+        if self.emergency_skip == EmergencySkip::TypePhrase
+            && self.emergency_skip_phrase.trim().is_empty()
+        {
+            return Err("The emergency skip phrase can't be empty".to_string());
+        }
+        if !(1..=300).contains(&self.hook_timeout_secs) {
+            return Err("Hook timeout must be between 1 and 300 seconds".to_string());
+        }
+        if self.day_start_hour > 23 {
+            return Err("The day must start between 0 and 23 o'clock".to_string());
+        }
+        if !(1..=50).contains(&self.daily_goal_sessions) {
+            return Err("The daily goal must be between 1 and 50 sessions".to_string());
+        }
+        if !(1..=1440).contains(&self.daily_goal_minutes) {
+            return Err("The daily goal must be between 1 and 1440 minutes".to_string());
+        }
+        for (i, block) in self.working_hours.iter().enumerate() {
+            if block.start >= block.end {
+                return Err(format!(
+                    "Working hours block {} must end after it starts",
+                    i + 1
+                ));
+            }
+            if !block.days.contains(&true) {
+                return Err(format!("Working hours block {} needs a day", i + 1));
+            }
+        }
+        for url in self.webhook_urls() {
+            if !is_webhook_url(&url) {
+                return Err(format!("\"{}\" isn't a valid http(s) URL", url));
+            }
+        }
+
+        let mut combos: Vec<(ShortcutAction, KeyCombo)> = Vec::new();
+        for action in ShortcutAction::ALL {
+            let combo: KeyCombo = self
+                .shortcut(action)
+                .parse()
+                .map_err(|e| format!("Shortcut for \"{}\": {}", action.description(), e))?;
+
+            if let Some((other, _)) = combos.iter().find(|(_, c)| *c == combo) {
+                return Err(format!(
+                    "\"{}\" and \"{}\" use the same shortcut",
+                    other.description(),
+                    action.description()
+                ));
+            }
+            combos.push((action, combo));
+        }
+
+        let mut hotkeys: Vec<(GlobalHotkeyAction, KeyCombo)> = Vec::new();
+        for action in GlobalHotkeyAction::ALL {
+            let binding = self.global_hotkey(action);
+            if binding.is_empty() {
+                continue;
+            }
+
+            let combo: KeyCombo = binding
+                .parse()
+                .map_err(|e| format!("Global hotkey for \"{}\": {}", action.description(), e))?;
+
+            if !is_global_hotkey(binding) {
+                return Err(format!(
+                    "Global hotkey for \"{}\" needs Cmd, Ctrl or Alt",
+                    action.description()
+                ));
+            }
+            if let Some((other, _)) = hotkeys.iter().find(|(_, c)| *c == combo) {
+                return Err(format!(
+                    "Global hotkeys \"{}\" and \"{}\" use the same keys",
+                    other.description(),
+                    action.description()
+                ));
+            }
+            // the global hotkey would fire before the window sees the key
+            if let Some((other, _)) = combos.iter().find(|(_, c)| *c == combo) {
+                return Err(format!(
+                    "Global hotkey for \"{}\" conflicts with the shortcut for \"{}\"",
+                    action.description(),
+                    other.description()
+                ));
+            }
+            hotkeys.push((action, combo));
+        }
+
+        Ok(())
+    }
+
+    pub fn shortcut(&self, action: ShortcutAction) -> &str {
+        // actions added in newer versions aren't in older settings files
+        self.shortcuts
+            .get(&action)
+            .map(String::as_str)
+            .unwrap_or(action.default_binding())
+    }
+
+    pub fn hook(&self, kind: SessionEventKind) -> &str {
+        self.hooks.get(&kind).map(String::as_str).unwrap_or("")
+    }
+
+    pub fn webhook_urls(&self) -> Vec<String> {
+        self.webhooks
+            .iter()
+            .map(|url| url.trim())
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn global_hotkey(&self, action: GlobalHotkeyAction) -> &str {
+        self.global_hotkeys
+            .get(&action)
+            .map(String::as_str)
+            .unwrap_or(action.default_binding())
+    }
+
+    /// The preset the current durations and sequence came from
+    pub fn current_preset(&self) -> Option<&Preset> {
+        self.presets
+            .iter()
+            .find(|preset| preset.name == self.active_preset && preset.matches(self))
+    }
+
+    /// Saves the current durations and sequence under `name`, replacing a preset with the same name
+    pub fn save_preset(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        let preset = Preset::from_settings(name, self);
+        match self.presets.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+        self.active_preset = name.to_string();
+    }
+
+    /// Switches to the durations and sequence of the preset called `name`
+    pub fn use_preset(&mut self, name: &str) {
+        let Some(preset) = self.presets.iter().find(|p| p.name == name).cloned() else {
+            return;
+        };
+
+        self.focus_minutes = preset.focus_minutes;
+        self.short_break_minutes = preset.short_break_minutes;
+        self.long_break_minutes = preset.long_break_minutes;
+        self.long_break_interval = preset.long_break_interval;
+        self.sequence = preset.sequence;
+        self.active_preset = preset.name;
+    }
+
+    /// The custom sequence, or focus and short breaks with a long break after every `long_break_interval` sessions
+    pub fn phases(&self) -> Vec<Phase> {
+        (0..self.phase_count()).map(|i| self.phase(i)).collect()
+    }
+
+    /// Length of the sequence, never zero
+    pub fn phase_count(&self) -> usize {
+        if self.sequence.is_empty() {
+            self.long_break_interval.max(1) as usize * 2
+        } else {
+            self.sequence.len()
+        }
+    }
+
+    /// Phase at `index` of the sequence, wrapping around at its end
+    pub fn phase(&self, index: usize) -> Phase {
+        if !self.sequence.is_empty() {
+            return self.sequence[index % self.sequence.len()].clone();
+        }
+
+        // focus sessions alternate with breaks, the last break of the cycle is the long one
+        let index = index % self.phase_count();
+        if index.is_multiple_of(2) {
+            Phase::new(
+                "Focus",
+                self.focus_minutes,
+                SessionKind::Focus,
+                PhaseColor::Blue,
+            )
+        } else if index == self.phase_count() - 1 {
+            Phase::new(
+                "Long break",
+                self.long_break_minutes,
+                SessionKind::LongBreak,
+                PhaseColor::Green,
+            )
+        } else {
+            Phase::new(
+                "Short break",
+                self.short_break_minutes,
+                SessionKind::ShortBreak,
+                PhaseColor::Green,
+            )
+        }
+    }
+
+    /// Duration of the phase at `index` in milliseconds
+    pub fn session_duration(&self, index: usize) -> u32 {
+        self.phase(index).duration()
+    }
+
+    /// The working hours block `at` falls into, if working hours are used
+    pub fn work_block_at(&self, at: DateTime<Local>) -> Option<&WorkBlock> {
+        if !self.use_working_hours {
+            return None;
+        }
+        self.working_hours.iter().find(|block| block.contains(at))
+    }
+
+    /// Always true without working hours
+    pub fn is_working_time(&self, at: DateTime<Local>) -> bool {
+        !self.use_working_hours || self.work_block_at(at).is_some()
+    }
+
+    /// Start of the day `at` belongs to, using `day_start_hour` as the boundary
+    pub fn day_start(&self, at: DateTime<Local>) -> DateTime<Local> {
+        let mut date = at.date_naive();
+        if at.hour() < self.day_start_hour {
+            date = date.pred_opt().unwrap_or(date);
+        }
+        let time = NaiveTime::from_hms_opt(self.day_start_hour, 0, 0).unwrap_or_default();
+        let start = date.and_time(time);
+        start
+            .and_local_timezone(Local)
+            .earliest()
+            // the boundary was skipped by a daylight saving change, which moves clocks by an hour
+            .or_else(|| {
+                (start + TimeDelta::hours(1))
+                    .and_local_timezone(Local)
+                    .earliest()
+            })
+            .unwrap_or(at)
+    }
+
+    pub fn auto_start(&self, phase: &Phase) -> bool {
+        if phase.is_focus() {
+            self.auto_start_focus
+        } else {
+            self.auto_start_breaks
+        }
+    }
+}
+
+fn is_webhook_url(url: &str) -> bool {
+    reqwest::Url::parse(url).is_ok_and(|parsed| matches!(parsed.scheme(), "http" | "https"))
+}
+
+/// Whether `binding` parses and has a modifier, a hotkey without one would swallow that key in every other app
+fn is_global_hotkey(binding: &str) -> bool {
+    binding
+        .parse::<KeyCombo>()
+        .is_ok_and(|combo| combo.meta || combo.ctrl || combo.alt)
+}
+
+fn settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("AuraTimer").join("settings.json"))
+}
+
+/// Switches to a preset from outside the settings panel, e.g. the main window or the tray
+pub fn switch_preset(name: &str) {
+    let mut settings = SETTINGS.peek().clone();
+    settings.use_preset(name);
+
+    // presets can be edited by hand in the settings file
+    if let Err(e) = settings.validate() {
+        error!("Can't switch to preset \"{}\": {}", name, e);
+        return;
+    }
+    update_settings(settings);
+}
+
+pub fn handle_settings_command(command: SettingsCommand) {
+    match command {
+        SettingsCommand::SwitchPreset(name) => switch_preset(&name),
+    }
+}
+
+/// Replaces the current settings, persists them and applies them to the running timer
+pub fn update_settings(settings: Settings) {
+    let previous = SETTINGS.peek().clone();
+    if previous == settings {
+        return;
+    }
+
+    if let Err(e) = settings.save() {
+        error!("Failed to save settings: {}", e);
+    }
+    let hotkeys_changed = previous.global_hotkeys != settings.global_hotkeys;
+    *SETTINGS.write() = settings;
+
+    apply_settings(&previous);
+    if hotkeys_changed {
+        register_global_hotkeys();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn kinds(settings: &Settings) -> Vec<SessionKind> {
+        settings.phases().iter().map(|phase| phase.kind).collect()
+    }
+
+    #[test]
+    fn classic_cycle_ends_with_the_long_break() {
+        use SessionKind::*;
+
+        let mut settings = Settings {
+            long_break_interval: 3,
+            ..Settings::default()
+        };
+        assert_eq!(
+            kinds(&settings),
+            [Focus, ShortBreak, Focus, ShortBreak, Focus, LongBreak]
+        );
+
+        // only reachable by editing the file, but must not leave the timer without phases
+        settings.long_break_interval = 0;
+        assert_eq!(kinds(&settings), [Focus, LongBreak]);
+        assert_eq!(settings.phase(7).kind, LongBreak);
+    }
+
+    #[test]
+    fn defaults_are_the_classic_preset() {
+        let settings = Settings::default();
+        assert_eq!(
+            settings.current_preset().map(|preset| preset.name.as_str()),
+            Some(CLASSIC_PRESET)
+        );
+    }
+
+    #[test]
+    fn loading_clamps_numbers_out_of_range() {
+        let settings = Settings::parse(
+            r#"{ "focus_minutes": 0, "long_break_interval": 4000000000, "sound_volume": 250,
+                 "sequence": [{ "name": "Deep work", "minutes": 100000, "kind": "Focus", "color": "Blue" }] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.focus_minutes, 1);
+        assert_eq!(settings.long_break_interval, 12);
+        assert_eq!(settings.sound_volume, 100);
+        assert_eq!(settings.sequence[0].minutes, 240);
+        assert_eq!(
+            settings.short_break_minutes,
+            Settings::default().short_break_minutes
+        );
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn loading_repairs_only_the_invalid_entries() {
+        let settings = Settings::parse(
+            r#"{ "focus_minutes": 40, "webhooks": ["not a url", "https://example.com/hook"],
+                 "sequence": [{ "name": " ", "minutes": 5, "kind": "Focus", "color": "Blue" }],
+                 "presets": [{ "name": "", "focus_minutes": 30, "short_break_minutes": 5,
+                               "long_break_minutes": 15, "long_break_interval": 4 }],
+                 "shortcuts": { "SkipSession": "Ctrl+" } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.focus_minutes, 40);
+        assert_eq!(settings.webhooks, ["https://example.com/hook"]);
+        assert_eq!(settings.sequence[0].name, "Focus");
+        assert!(settings.presets.is_empty());
+        assert_eq!(
+            settings.shortcut(ShortcutAction::SkipSession),
+            ShortcutAction::SkipSession.default_binding()
+        );
+        assert_eq!(settings.validate(), Ok(()));
+    }
+
+    #[test]
+    fn loading_fails_when_the_file_cant_be_repaired() {
+        assert!(Settings::parse("{ broken").is_err());
+        // two shortcuts on the same keys, either could be the mistake
+        assert!(
+            Settings::parse(r#"{ "shortcuts": { "SkipSession": "Alt+S", "Undo": "Alt+S" } }"#)
+                .is_err()
+        );
+    }
+
+    fn any_phase() -> impl Strategy<Value = Phase> {
+        (
+            any::<u32>(),
+            prop::sample::select(SessionKind::ALL.to_vec()),
+        )
+            .prop_map(|(minutes, kind)| Phase::new("Phase", minutes, kind, PhaseColor::Blue))
+    }
+
+    proptest! {
+        #[test]
+        fn phase_is_defined_for_any_settings(
+            focus_minutes in any::<u32>(),
+            short_break_minutes in any::<u32>(),
+            long_break_minutes in any::<u32>(),
+            long_break_interval in 0u32..100,
+            sequence in prop::collection::vec(any_phase(), 0..6),
+            index in any::<usize>(),
+        ) {
+            let settings = Settings {
+                focus_minutes,
+                short_break_minutes,
+                long_break_minutes,
+                long_break_interval,
+                sequence,
+                ..Settings::default()
+            };
+
+            let phases = settings.phases();
+            prop_assert_eq!(phases.len(), settings.phase_count());
+            let phase = settings.phase(index);
+            prop_assert_eq!(&phase, &phases[index % phases.len()]);
+
+            let millis = (phase.minutes as u64 * 60 * 1000).min(u32::MAX as u64);
+            prop_assert_eq!(phase.duration() as u64, millis);
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        settings_panel::{close_settings, toggle_settings},
+        shortcuts_overlay::toggle_shortcuts,
+        timer::{next_session, redo, restart_or_revert, toggle_timer, undo},
+        timer_expired::dismiss_expiry,
+    },
+    interruptions::{close_interruption_prompt, open_interruption_prompt, INTERRUPTION_PROMPT},
+    settings::SETTINGS,
+    state::{SHOW_REMINDERS, SHOW_SETTINGS, SHOW_SHORTCUTS, SHOW_STATS, SHOW_TASKS, TIMER_EXPIRED},
+};
+
+// whether an input, select or textarea has the keyboard focus, keys go to it instead of the shortcuts
+static TEXT_FOCUSED: GlobalSignal<bool> = GlobalSignal::new(|| false);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ShortcutAction {
+    ToggleTimer,
+    SkipSession,
+    RestartOrRevert,
+    Undo,
+    Redo,
+    LogInterruption,
+    DismissExpiry,
+    OpenSettings,
+    ShowShortcuts,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 9] = [
+        ShortcutAction::ToggleTimer,
+        ShortcutAction::SkipSession,
+        ShortcutAction::RestartOrRevert,
+        ShortcutAction::Undo,
+        ShortcutAction::Redo,
+        ShortcutAction::LogInterruption,
+        ShortcutAction::DismissExpiry,
+        ShortcutAction::OpenSettings,
+        ShortcutAction::ShowShortcuts,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleTimer => "Start / pause timer",
+            ShortcutAction::SkipSession => "Skip session",
+            ShortcutAction::RestartOrRevert => "Restart timer / restore skipped session",
+            ShortcutAction::Undo => "Undo skip, restart, start or snooze",
+            ShortcutAction::Redo => "Redo",
+            ShortcutAction::LogInterruption => "Log an interruption",
+            ShortcutAction::DismissExpiry => "Dismiss expired timer",
+            ShortcutAction::OpenSettings => "Open settings",
+            ShortcutAction::ShowShortcuts => "Show keyboard shortcuts",
+        }
+    }
+
+    pub fn default_binding(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleTimer => "Space",
+            ShortcutAction::SkipSession => "S",
+            ShortcutAction::RestartOrRevert => "R",
+            ShortcutAction::Undo => "CmdOrCtrl+Z",
+            ShortcutAction::Redo => "CmdOrCtrl+Shift+Z",
+            ShortcutAction::LogInterruption => "I",
+            ShortcutAction::DismissExpiry => "Escape",
+            ShortcutAction::OpenSettings => "CmdOrCtrl+,",
+            ShortcutAction::ShowShortcuts => "Shift+/",
+        }
+    }
+}
+
+pub fn default_shortcuts() -> BTreeMap<ShortcutAction, String> {
+    ShortcutAction::ALL
+        .into_iter()
+        .map(|action| (action, action.default_binding().to_string()))
+        .collect()
+}
+
+/// A key with modifiers, parsed from strings like "CmdOrCtrl+Shift+S"
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyCombo {
+    /// Physical key code as named by the DOM `code` property, e.g. "KeyS" or "Space"
+    pub code: String,
+    pub meta: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl FromStr for KeyCombo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let Some((key, modifiers)) = parts.split_last() else {
+            return Err("Shortcut is empty".to_string());
+        };
+
+        let mut combo = KeyCombo::default();
+        for modifier in modifiers {
+            match modifier.to_lowercase().as_str() {
+                "cmd" | "command" | "super" | "meta" => combo.meta = true,
+                "ctrl" | "control" => combo.ctrl = true,
+                "cmdorctrl" | "commandorcontrol" => {
+                    if cfg!(target_os = "macos") {
+                        combo.meta = true;
+                    } else {
+                        combo.ctrl = true;
+                    }
+                }
+                "alt" | "option" => combo.alt = true,
+                "shift" => combo.shift = true,
+                _ => return Err(format!("Unknown modifier \"{}\"", modifier)),
+            }
+        }
+
+        combo.code = key_code(key).ok_or_else(|| format!("Unknown key \"{}\"", key))?;
+        Ok(combo)
+    }
+}
+
+impl KeyCombo {
+    pub fn matches(&self, e: &KeyboardEvent) -> bool {
+        let modifiers = e.modifiers();
+
+        e.code().to_string() == self.code
+            && modifiers.contains(Modifiers::META) == self.meta
+            && modifiers.contains(Modifiers::CONTROL) == self.ctrl
+            && modifiers.contains(Modifiers::ALT) == self.alt
+            && modifiers.contains(Modifiers::SHIFT) == self.shift
+    }
+}
+
+const NAMED_KEYS: &[&str] = &[
+    "Space",
+    "Enter",
+    "Escape",
+    "Tab",
+    "Backspace",
+    "Delete",
+    "Home",
+    "End",
+    "PageUp",
+    "PageDown",
+    "ArrowUp",
+    "ArrowDown",
+    "ArrowLeft",
+    "ArrowRight",
+    "Comma",
+    "Period",
+    "Slash",
+    "F1",
+    "F2",
+    "F3",
+    "F4",
+    "F5",
+    "F6",
+    "F7",
+    "F8",
+    "F9",
+    "F10",
+    "F11",
+    "F12",
+    "Minus",
+    "Equal",
+];
+
+fn key_code(key: &str) -> Option<String> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        let code = match c {
+            'a'..='z' | 'A'..='Z' => return Some(format!("Key{}", c.to_ascii_uppercase())),
+            '0'..='9' => return Some(format!("Digit{}", c)),
+            ',' => "Comma",
+            '.' => "Period",
+            '/' => "Slash",
+            ';' => "Semicolon",
+            '\'' => "Quote",
+            '[' => "BracketLeft",
+            ']' => "BracketRight",
+            '-' => "Minus",
+            '=' => "Equal",
+            '`' => "Backquote",
+            '\\' => "Backslash",
+            _ => return None,
+        };
+        return Some(code.to_string());
+    }
+
+    if key.eq_ignore_ascii_case("esc") {
+        return Some("Escape".to_string());
+    }
+
+    NAMED_KEYS
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(key))
+        .map(|name| name.to_string())
+}
+
+fn trigger(action: ShortcutAction) {
+    let timer_expired = *TIMER_EXPIRED.peek();
+
+    match action {
+        ShortcutAction::ToggleTimer if !timer_expired => toggle_timer(),
+        ShortcutAction::SkipSession if !timer_expired => next_session(),
+        ShortcutAction::RestartOrRevert if !timer_expired => restart_or_revert(),
+        ShortcutAction::Undo => undo(),
+        ShortcutAction::Redo => redo(),
+        ShortcutAction::LogInterruption => open_interruption_prompt(),
+        ShortcutAction::DismissExpiry if timer_expired => dismiss_expiry(),
+        ShortcutAction::OpenSettings => toggle_settings(),
+        ShortcutAction::ShowShortcuts => toggle_shortcuts(),
+        _ => {}
+    }
+}
+
+/// Follows the keyboard focus in the webview, since key events don't say which element they come from.
+/// Used once in the main window.
+pub fn use_focus_tracking() {
+    use_future(|| async {
+        let mut focus = document::eval(
+            r#"
+            const isTextField = (element) =>
+                element instanceof HTMLElement && ["INPUT", "SELECT", "TEXTAREA"].includes(element.tagName);
+            document.addEventListener("focusin", (e) => dioxus.send(isTextField(e.target)));
+            document.addEventListener("focusout", (e) => dioxus.send(isTextField(e.relatedTarget)));
+            await new Promise(() => {});
+            "#,
+        );
+
+        while let Ok(text_focused) = focus.recv::<bool>().await {
+            *TEXT_FOCUSED.write() = text_focused;
+        }
+    });
+}
+
+pub fn handle_keydown(e: KeyboardEvent) {
+    if e.is_auto_repeating() {
+        return;
+    }
+
+    // Escape always closes whatever is layered on top of the timer first
+    if e.code() == Code::Escape {
+        if *SHOW_SHORTCUTS.peek() {
+            *SHOW_SHORTCUTS.write() = false;
+            return;
+        }
+        if *SHOW_STATS.peek() {
+            *SHOW_STATS.write() = false;
+            return;
+        }
+        if *SHOW_TASKS.peek() {
+            *SHOW_TASKS.write() = false;
+            return;
+        }
+        if *SHOW_REMINDERS.peek() {
+            *SHOW_REMINDERS.write() = false;
+            return;
+        }
+        if *INTERRUPTION_PROMPT.peek() {
+            close_interruption_prompt();
+            return;
+        }
+        if *SHOW_SETTINGS.peek() {
+            close_settings();
+            return;
+        }
+    }
+
+    // typing into a field shouldn't control the timer, Escape above still closes what it's in
+    if *TEXT_FOCUSED.peek() {
+        return;
+    }
+
+    let Some(action) = ShortcutAction::ALL.into_iter().find(|action| {
+        SETTINGS
+            .peek()
+            .shortcut(*action)
+            .parse::<KeyCombo>()
+            .is_ok_and(|combo| combo.matches(&e))
+    }) else {
+        return;
+    };
+
+    // typing into the settings fields shouldn't control the timer
+    if *SHOW_SETTINGS.peek() && action != ShortcutAction::OpenSettings {
+        return;
+    }
+
+    e.prevent_default();
+    trigger(action);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hotkeys::GlobalHotkeyAction, settings::Settings};
+
+    #[test]
+    fn parses_modifiers_and_keys() {
+        assert_eq!(
+            "Ctrl + Shift + s".parse(),
+            Ok(KeyCombo {
+                code: "KeyS".to_string(),
+                ctrl: true,
+                shift: true,
+                ..KeyCombo::default()
+            })
+        );
+        assert_eq!(
+            "Option+esc".parse(),
+            Ok(KeyCombo {
+                code: "Escape".to_string(),
+                alt: true,
+                ..KeyCombo::default()
+            })
+        );
+        assert_eq!("Shift+/".parse::<KeyCombo>().unwrap().code, "Slash");
+        assert_eq!("pageup".parse::<KeyCombo>().unwrap().code, "PageUp");
+        assert_eq!("7".parse::<KeyCombo>().unwrap().code, "Digit7");
+
+        let combo: KeyCombo = "CmdOrCtrl+Z".parse().unwrap();
+        assert_eq!(combo.meta, cfg!(target_os = "macos"));
+        assert_eq!(combo.ctrl, !cfg!(target_os = "macos"));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert_eq!(
+            "Hyper+S".parse::<KeyCombo>(),
+            Err("Unknown modifier \"Hyper\"".to_string())
+        );
+        assert_eq!(
+            "Ctrl+Scroll".parse::<KeyCombo>(),
+            Err("Unknown key \"Scroll\"".to_string())
+        );
+        assert!("Ctrl+".parse::<KeyCombo>().is_err());
+    }
+
+    #[test]
+    fn every_default_binding_parses() {
+        for action in ShortcutAction::ALL {
+            assert!(action.default_binding().parse::<KeyCombo>().is_ok());
+        }
+        assert_eq!(Settings::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn the_same_keys_for_two_actions_are_a_conflict() {
+        let mut settings = Settings::default();
+        // spelled differently, but the same keys
+        settings
+            .shortcuts
+            .insert(ShortcutAction::SkipSession, "space".to_string());
+        assert_eq!(
+            settings.validate(),
+            Err("\"Start / pause timer\" and \"Skip session\" use the same shortcut".to_string())
+        );
+
+        let mut settings = Settings::default();
+        settings
+            .shortcuts
+            .insert(ShortcutAction::SkipSession, "Ctrl+Alt+S".to_string());
+        settings
+            .global_hotkeys
+            .insert(GlobalHotkeyAction::SkipSession, "Control+Alt+S".to_string());
+        assert!(settings
+            .validate()
+            .is_err_and(|e| e.contains("conflicts with the shortcut for \"Skip session\"")));
+    }
+}
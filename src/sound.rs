@@ -1,10 +1,13 @@
+use dioxus::signals::Readable;
 use rodio::{Decoder, OutputStream, Source};
 use std::fs::File;
 use std::io::BufReader;
 use std::thread;
 use tracing::error;
 
-pub fn play_sound(path: &str) -> Result<(), Box<dyn std::error::Error>> {
+use crate::settings::SETTINGS;
+
+pub fn play_sound(path: &str, volume: f32) -> Result<(), Box<dyn std::error::Error>> {
     let path = path.to_string();
 
     thread::spawn(move || {
@@ -13,7 +16,7 @@ pub fn play_sound(path: &str) -> Result<(), Box<dyn std::error::Error>> {
             let file = BufReader::new(File::open(&path)?);
             let source = Decoder::new(file)?;
 
-            stream_handle.play_raw(source.convert_samples())?;
+            stream_handle.play_raw(source.amplify(volume).convert_samples())?;
 
             thread::sleep(std::time::Duration::from_secs(1));
             Ok(())
@@ -28,7 +31,13 @@ pub fn play_sound(path: &str) -> Result<(), Box<dyn std::error::Error>> {
 }
 
 pub fn play_alarm() {
-    let _ = play_sound("assets/alarm.wav").map_err(|e| {
+    let settings = SETTINGS.peek();
+    if !settings.play_sound {
+        return;
+    }
+
+    let volume = settings.sound_volume as f32 / 100.0;
+    let _ = play_sound("assets/alarm.wav", volume).map_err(|e| {
         error!("Failed to play alarm sound: {}", e);
     });
 }
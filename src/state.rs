@@ -1,21 +1,24 @@
-use std::sync::Mutex;
-
 use dioxus::{
-    desktop::tao::window::WindowId,
     hooks::use_effect,
     signals::{GlobalSignal, Readable},
 };
 
+use crate::settings::{Theme, SETTINGS};
+
 pub static TIMER_EXPIRED: GlobalSignal<bool> = GlobalSignal::new(|| false);
-pub static IS_FOCUS_MODE: GlobalSignal<bool> = GlobalSignal::new(|| true);
+/// Position in the session sequence, see `Settings::phases`
+pub static PHASE_INDEX: GlobalSignal<usize> = GlobalSignal::new(|| 0);
 
+/// Finished focus sessions
 pub static SMALL_SESSION_COUNT: GlobalSignal<u32> = GlobalSignal::new(|| 0);
+/// Times the whole sequence was run through
 pub static FULL_SESSION_COUNT: GlobalSignal<u32> = GlobalSignal::new(|| 0);
 
-pub static IS_FOCUS_MODE_MUTEX: Mutex<bool> = Mutex::new(false);
-pub static SMALL_SESSION_COUNT_MUTEX: Mutex<u32> = Mutex::new(0);
-
-pub static ALERT_WINDOW_ID: Mutex<Option<WindowId>> = Mutex::new(None);
+pub static SHOW_SETTINGS: GlobalSignal<bool> = GlobalSignal::new(|| false);
+pub static SHOW_SHORTCUTS: GlobalSignal<bool> = GlobalSignal::new(|| false);
+pub static SHOW_STATS: GlobalSignal<bool> = GlobalSignal::new(|| false);
+pub static SHOW_TASKS: GlobalSignal<bool> = GlobalSignal::new(|| false);
+pub static SHOW_REMINDERS: GlobalSignal<bool> = GlobalSignal::new(|| false);
 
 pub static BG_COLOR: GlobalSignal<&str> = GlobalSignal::new(|| "bg-blue-200");
 pub static BG_COLOR_HOVER: GlobalSignal<&str> = GlobalSignal::new(|| "hover:bg-blue-500");
@@ -27,54 +30,48 @@ pub static ICON_COLOR: GlobalSignal<&str> = GlobalSignal::new(|| "fill-blue-500
 pub fn init_colors() {
     use_effect(|| {
         let timer_expired = *TIMER_EXPIRED.read();
-        let is_focus_mode = *IS_FOCUS_MODE.read();
+        let settings = SETTINGS.read();
+        let theme = settings.theme;
+        let classes = settings.phase(*PHASE_INDEX.read()).color.classes();
 
-        let bg_color = if timer_expired {
+        let bg_color = if theme == Theme::Dark {
+            "bg-neutral-900"
+        } else if timer_expired {
             "bg-red-200"
-        } else if is_focus_mode {
-            "bg-blue-200"
         } else {
-            "bg-green-200"
+            classes.bg
         };
 
         let bg_hover = if timer_expired {
             "hover:bg-red-500"
-        } else if is_focus_mode {
-            "hover:bg-blue-500"
         } else {
-            "hover:bg-green-500"
+            classes.bg_hover
         };
 
         let bg_color_inverted = if timer_expired {
             "bg-red-500"
-        } else if is_focus_mode {
-            "bg-blue-500"
         } else {
-            "bg-green-500"
+            classes.bg_inverted
         };
 
         let text_color = if timer_expired {
             "text-red-500"
-        } else if is_focus_mode {
-            "text-blue-500"
         } else {
-            "text-green-500"
+            classes.text
         };
 
-        let text_color_inverted = if timer_expired {
+        let text_color_inverted = if theme == Theme::Dark {
+            "text-neutral-900"
+        } else if timer_expired {
             "text-red-200"
-        } else if is_focus_mode {
-            "text-blue-200"
         } else {
-            "text-green-200"
+            classes.text_inverted
         };
 
         let icon_color = if timer_expired {
             "fill-red-500 stroke-red-500"
-        } else if is_focus_mode {
-            "fill-blue-500 stroke-blue-500"
         } else {
-            "fill-green-500 stroke-green-500"
+            classes.icon
         };
 
         *BG_COLOR.write() = bg_color;
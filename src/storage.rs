@@ -0,0 +1,39 @@
+use std::{fs, io, path::Path};
+
+use serde::Serialize;
+
+/// Saves `value` as pretty JSON at `path`, creating its directory if needed
+pub fn write_json(path: &Path, value: &impl Serialize) -> Result<(), Box<dyn std::error::Error>> {
+    write_atomically(path, &serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// Writes a temporary file next to `path` and moves it into place, so readers never see a half written file
+/// and a crash while saving leaves the previous version
+pub fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_replace_the_file_as_a_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data").join("history.json");
+
+        write_json(&path, &[1]).unwrap();
+        write_json(&path, &[1, 2]).unwrap();
+
+        let saved: Vec<u32> = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved, [1, 2]);
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+}
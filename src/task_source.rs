@@ -0,0 +1,332 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use chrono::Local;
+use dioxus::prelude::*;
+use tracing::error;
+
+use crate::{settings::SETTINGS, tasks::merge_imported_tasks};
+
+/// Why the task source couldn't be read or written, shown in the task list
+pub static TASK_SOURCE_ERROR: GlobalSignal<Option<String>> = GlobalSignal::new(|| None);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SourceFormat {
+    TodoTxt,
+    /// `- [ ]` checklists, other lines are ignored
+    Markdown,
+}
+
+impl SourceFormat {
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("md" | "markdown") => SourceFormat::Markdown,
+            _ => SourceFormat::TodoTxt,
+        }
+    }
+}
+
+/// A task as written in the source file
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceTask {
+    pub name: String,
+    pub done: bool,
+    /// Number of tasks with the same name above it
+    pub occurrence: usize,
+}
+
+fn is_date(s: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()
+}
+
+/// Priorities like "(A)" in todo.txt
+fn is_priority(s: &str) -> bool {
+    s.len() == 3 && s.starts_with('(') && s.ends_with(')')
+}
+
+/// Removes a leading word if it matches, e.g. dates and priorities in todo.txt
+fn strip_word(s: &str, matches: impl Fn(&str) -> bool) -> &str {
+    match s.split_once(' ') {
+        Some((word, rest)) if matches(word) => rest.trim_start(),
+        _ => s,
+    }
+}
+
+/// Rewrites todo.txt's "+project" and "@context" into the "@project" and "#tag" of task labels
+fn todo_txt_to_label(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            if let Some(project) = word.strip_prefix('+').filter(|p| !p.is_empty()) {
+                format!("@{}", project)
+            } else if let Some(context) = word.strip_prefix('@').filter(|c| !c.is_empty()) {
+                format!("#{}", context)
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_todo_txt_line(line: &str) -> Option<SourceTask> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    let (done, rest) = match line.strip_prefix("x ") {
+        Some(rest) => (true, strip_word(rest.trim_start(), is_date)),
+        None => (false, line),
+    };
+    let rest = strip_word(rest, is_priority);
+    let name = strip_word(rest, is_date);
+
+    Some(SourceTask {
+        name: todo_txt_to_label(name),
+        done,
+        occurrence: 0,
+    })
+}
+
+/// Splits a checklist line into the part before the checkbox, whether it's checked and the text after it
+fn split_checkbox(line: &str) -> Option<(&str, bool, &str)> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+
+    let rest = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| rest.strip_prefix(bullet))?;
+    let (done, text) = if let Some(text) = rest.strip_prefix("[ ]") {
+        (false, text)
+    } else if let Some(text) = rest.strip_prefix("[x]").or(rest.strip_prefix("[X]")) {
+        (true, text)
+    } else {
+        return None;
+    };
+
+    Some((&line[..indent + 2], done, text.trim()))
+}
+
+fn parse_line(format: SourceFormat, line: &str) -> Option<SourceTask> {
+    match format {
+        SourceFormat::TodoTxt => parse_todo_txt_line(line),
+        SourceFormat::Markdown => {
+            let (_, done, name) = split_checkbox(line)?;
+            (!name.is_empty()).then(|| SourceTask {
+                name: name.to_string(),
+                done,
+                occurrence: 0,
+            })
+        }
+    }
+}
+
+fn set_line_done(format: SourceFormat, line: &str, done: bool) -> String {
+    match format {
+        SourceFormat::TodoTxt => {
+            let line = line.trim();
+            match (line.strip_prefix("x "), done) {
+                // completed tasks don't keep their priority
+                (None, true) => format!(
+                    "x {} {}",
+                    Local::now().format("%Y-%m-%d"),
+                    strip_word(line, is_priority)
+                ),
+                (Some(rest), false) => strip_word(rest.trim_start(), is_date).to_string(),
+                _ => line.to_string(),
+            }
+        }
+        SourceFormat::Markdown => match split_checkbox(line) {
+            Some((prefix, _, text)) => {
+                format!("{}[{}] {}", prefix, if done { "x" } else { " " }, text)
+            }
+            None => line.to_string(),
+        },
+    }
+}
+
+fn source_path() -> Option<PathBuf> {
+    let path = SETTINGS.peek().task_source.trim().to_string();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// The tasks in `contents` with the line each one is on
+fn parse_source(format: SourceFormat, contents: &str) -> Vec<(usize, SourceTask)> {
+    let mut tasks: Vec<(usize, SourceTask)> = Vec::new();
+    for (line_number, line) in contents.lines().enumerate() {
+        if let Some(mut task) = parse_line(format, line) {
+            task.occurrence = tasks.iter().filter(|(_, t)| t.name == task.name).count();
+            tasks.push((line_number, task));
+        }
+    }
+    tasks
+}
+
+fn read_source(path: &Path) -> Result<Vec<SourceTask>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    let format = SourceFormat::from_path(path);
+
+    Ok(parse_source(format, &contents)
+        .into_iter()
+        .map(|(_, task)| task)
+        .collect())
+}
+
+/// `contents` with the task `name` checked or unchecked, `None` if there's nothing to change
+fn set_done_in_contents(
+    format: SourceFormat,
+    contents: &str,
+    name: &str,
+    occurrence: usize,
+    done: bool,
+) -> Option<String> {
+    let (line_number, _) = parse_source(format, contents)
+        .into_iter()
+        .find(|(_, task)| {
+            task.name == name && task.occurrence == occurrence && task.done != done
+        })?;
+
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    lines[line_number] = set_line_done(format, &lines[line_number], done);
+    let mut changed = lines.join("\n");
+    if contents.ends_with('\n') {
+        changed.push('\n');
+    }
+    Some(changed)
+}
+
+/// Reads the task source and updates the imported tasks in the task list
+pub fn sync_task_source() {
+    let Some(path) = source_path() else {
+        merge_imported_tasks(Vec::new());
+        *TASK_SOURCE_ERROR.write() = None;
+        return;
+    };
+
+    match read_source(&path) {
+        Ok(tasks) => {
+            merge_imported_tasks(tasks);
+            *TASK_SOURCE_ERROR.write() = None;
+        }
+        Err(e) => {
+            error!("Failed to import tasks: {}", e);
+            *TASK_SOURCE_ERROR.write() = Some(e);
+        }
+    }
+}
+
+/// Checks or unchecks a task in the task source, `occurrence` tells tasks with the same name apart
+pub fn set_done_in_source(name: &str, occurrence: usize, done: bool) {
+    let Some(path) = source_path() else {
+        return;
+    };
+
+    let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(&path)?;
+        let format = SourceFormat::from_path(&path);
+
+        if let Some(changed) = set_done_in_contents(format, &contents, name, occurrence, done) {
+            fs::write(&path, changed)?;
+        }
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        error!("Failed to update task source: {}", e);
+        *TASK_SOURCE_ERROR.write() = Some(format!("Couldn't update {}: {}", path.display(), e));
+    }
+}
+
+/// Re-imports the task source whenever the file or the configured path changes
+pub fn use_task_source_watcher() {
+    use_future(|| async {
+        let mut interval = tokio::time::interval(Duration::from_secs(2));
+        // None until the first check, so tasks of a removed source are cleaned up on launch too
+        let mut last_seen: Option<Option<(PathBuf, Option<SystemTime>)>> = None;
+
+        loop {
+            interval.tick().await;
+
+            let current = source_path().map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, modified)
+            });
+            if last_seen.as_ref() != Some(&current) {
+                sync_task_source();
+                last_seen = Some(current);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::TaskLabel;
+
+    #[test]
+    fn tasks_with_the_same_name_are_counted_apart() {
+        let contents = "- [ ] Email\n- [x] Review\n- [x] Email\n";
+        let tasks: Vec<_> = parse_source(SourceFormat::Markdown, contents)
+            .into_iter()
+            .map(|(_, task)| (task.name, task.done, task.occurrence))
+            .collect();
+        assert_eq!(
+            tasks,
+            [
+                ("Email".to_string(), false, 0),
+                ("Review".to_string(), true, 0),
+                ("Email".to_string(), true, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn todo_txt_projects_and_contexts_become_the_label() {
+        let task = parse_todo_txt_line("(B) 2024-05-01 Call mom +family @phone").unwrap();
+        assert_eq!(task.name, "Call mom @family #phone");
+        assert_eq!(
+            TaskLabel::parse(&task.name),
+            Some(TaskLabel {
+                name: "Call mom".to_string(),
+                project: Some("family".to_string()),
+                tag: Some("phone".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn only_the_given_occurrence_is_changed() {
+        let contents = "- [ ] Email\n- [ ] Email\n";
+        let changed = set_done_in_contents(SourceFormat::Markdown, contents, "Email", 1, true);
+        assert_eq!(changed.as_deref(), Some("- [ ] Email\n- [x] Email\n"));
+
+        // already done, nothing to write
+        let changed = changed.unwrap();
+        assert_eq!(
+            set_done_in_contents(SourceFormat::Markdown, &changed, "Email", 1, true),
+            None
+        );
+    }
+
+    #[test]
+    fn completing_a_todo_txt_task_drops_its_priority() {
+        let changed =
+            set_done_in_contents(SourceFormat::TodoTxt, "(A) Call mom\n", "Call mom", 0, true)
+                .unwrap();
+        let today = Local::now().format("%Y-%m-%d");
+        assert_eq!(changed, format!("x {} Call mom\n", today));
+        assert_eq!(
+            parse_source(SourceFormat::TodoTxt, &changed)[0].1,
+            SourceTask {
+                name: "Call mom".to_string(),
+                done: true,
+                occurrence: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use std::{fs, path::PathBuf};
+
+use dioxus::signals::{GlobalSignal, Readable};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    history::{TaskLabel, CURRENT_TASK},
+    storage::write_json,
+    task_source::{set_done_in_source, SourceTask},
+};
+
+pub static TASK_LIST: GlobalSignal<TaskList> = GlobalSignal::new(TaskList::load);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub id: u64,
+    pub name: String,
+    pub estimated_pomodoros: u32,
+    pub actual_pomodoros: u32,
+    pub done: bool,
+    pub archived: bool,
+    /// Comes from the todo.txt or Markdown file set in the settings, matched by name and `occurrence`
+    #[serde(default)]
+    pub imported: bool,
+    /// Which of the imported tasks with the same name it is, counting from 0
+    #[serde(default)]
+    pub occurrence: usize,
+}
+
+impl Task {
+    /// The name may contain a project and tag like "Write report @work #writing"
+    pub fn label(&self) -> Option<TaskLabel> {
+        TaskLabel::parse(&self.name)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskList {
+    pub tasks: Vec<Task>,
+    /// Task that finished focus sessions are counted towards
+    pub active_task: Option<u64>,
+}
+
+impl TaskList {
+    fn load() -> Self {
+        let Some(path) = tasks_path() else {
+            return Self::default();
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                error!("Failed to parse task list: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = tasks_path().ok_or("No data directory available")?;
+        write_json(&path, self)
+    }
+
+    pub fn active(&self) -> Option<&Task> {
+        let id = self.active_task?;
+        self.tasks.iter().find(|task| task.id == id)
+    }
+
+    fn next_id(&self) -> u64 {
+        self.tasks
+            .iter()
+            .map(|task| task.id)
+            .max()
+            .map_or(0, |id| id + 1)
+    }
+
+    fn task_mut(&mut self, id: u64) -> Option<&mut Task> {
+        self.tasks.iter_mut().find(|task| task.id == id)
+    }
+
+    /// A finished or archived task can't stay active
+    fn deactivate_if_done(&mut self) {
+        if self.active().is_none_or(|task| task.done || task.archived) {
+            self.active_task = None;
+        }
+    }
+
+    /// Replaces the imported tasks with `source_tasks`, keeping estimates and counts of known tasks
+    fn merge_imported(&mut self, source_tasks: Vec<SourceTask>) {
+        // tasks with the same name are told apart by their order in the source
+        let matches = |task: &Task, source_task: &SourceTask| {
+            task.imported
+                && task.name == source_task.name
+                && task.occurrence == source_task.occurrence
+        };
+
+        self.tasks
+            .retain(|task| !task.imported || source_tasks.iter().any(|t| matches(task, t)));
+        for source_task in source_tasks {
+            match self
+                .tasks
+                .iter_mut()
+                .find(|task| matches(task, &source_task))
+            {
+                Some(task) => task.done = source_task.done,
+                None => {
+                    let id = self.next_id();
+                    self.tasks.push(Task {
+                        id,
+                        name: source_task.name,
+                        estimated_pomodoros: 1,
+                        actual_pomodoros: 0,
+                        done: source_task.done,
+                        archived: false,
+                        imported: true,
+                        occurrence: source_task.occurrence,
+                    });
+                }
+            }
+        }
+        self.deactivate_if_done();
+    }
+
+    /// Moves the task up (negative offset) or down among the tasks that aren't archived
+    fn move_task(&mut self, id: u64, offset: isize) {
+        let visible: Vec<usize> = (0..self.tasks.len())
+            .filter(|i| !self.tasks[*i].archived)
+            .collect();
+        let Some(position) = visible.iter().position(|i| self.tasks[*i].id == id) else {
+            return;
+        };
+        let Some(&target) = position
+            .checked_add_signed(offset)
+            .and_then(|target| visible.get(target))
+        else {
+            return;
+        };
+
+        self.tasks.swap(visible[position], target);
+    }
+}
+
+fn tasks_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("AuraTimer").join("tasks.json"))
+}
+
+/// Label of the active task, used as the initial task label on launch
+pub fn active_task_label() -> Option<TaskLabel> {
+    TASK_LIST.peek().active().and_then(Task::label)
+}
+
+/// Changes and saves the task list, the task label follows the active task
+fn update_task_list(update: impl FnOnce(&mut TaskList)) {
+    let active_task = TASK_LIST.peek().active_task;
+    update(&mut TASK_LIST.write());
+
+    if let Err(e) = TASK_LIST.peek().save() {
+        error!("Failed to save task list: {}", e);
+    }
+    if TASK_LIST.peek().active_task != active_task {
+        *CURRENT_TASK.write() = active_task_label();
+    }
+}
+
+fn update_task(id: u64, update: impl FnOnce(&mut Task)) {
+    update_task_list(|list| {
+        if let Some(task) = list.task_mut(id) {
+            update(task);
+        }
+        list.deactivate_if_done();
+    });
+}
+
+pub fn add_task(name: &str, estimated_pomodoros: u32) {
+    let name = name.trim();
+    if name.is_empty() {
+        return;
+    }
+
+    update_task_list(|list| {
+        let id = list.next_id();
+        list.tasks.push(Task {
+            id,
+            name: name.to_string(),
+            estimated_pomodoros,
+            actual_pomodoros: 0,
+            done: false,
+            archived: false,
+            imported: false,
+            occurrence: 0,
+        });
+    });
+}
+
+/// Replaces the imported tasks with the ones from the task source, keeping estimates and counts of known tasks
+pub fn merge_imported_tasks(source_tasks: Vec<SourceTask>) {
+    let mut list = TASK_LIST.peek().clone();
+    list.merge_imported(source_tasks);
+
+    if list != *TASK_LIST.peek() {
+        update_task_list(|current| *current = list);
+    }
+}
+
+/// Makes the task the one focus sessions are counted towards, `None` to not count them at all
+pub fn activate_task(id: Option<u64>) {
+    update_task_list(|list| list.active_task = id);
+}
+
+pub fn set_task_done(id: u64, done: bool) {
+    let mut imported = None;
+    update_task(id, |task| {
+        task.done = done;
+        if task.imported {
+            imported = Some((task.name.clone(), task.occurrence));
+        }
+    });
+    if let Some((name, occurrence)) = imported {
+        set_done_in_source(&name, occurrence, done);
+    }
+}
+
+pub fn set_task_archived(id: u64, archived: bool) {
+    update_task(id, |task| task.archived = archived);
+}
+
+pub fn set_task_estimate(id: u64, estimated_pomodoros: u32) {
+    update_task(id, |task| task.estimated_pomodoros = estimated_pomodoros);
+}
+
+/// Moves the task up (negative offset) or down among the tasks that aren't archived
+pub fn move_task(id: u64, offset: isize) {
+    update_task_list(|list| list.move_task(id, offset));
+}
+
+/// Counts a finished focus session towards the active task
+pub fn complete_pomodoro() {
+    let Some(id) = TASK_LIST.peek().active_task else {
+        return;
+    };
+    update_task(id, |task| task.actual_pomodoros += 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: u64, name: &str) -> Task {
+        Task {
+            id,
+            name: name.to_string(),
+            estimated_pomodoros: 1,
+            actual_pomodoros: 0,
+            done: false,
+            archived: false,
+            imported: false,
+            occurrence: 0,
+        }
+    }
+
+    fn source_task(name: &str, done: bool, occurrence: usize) -> SourceTask {
+        SourceTask {
+            name: name.to_string(),
+            done,
+            occurrence,
+        }
+    }
+
+    fn ids(list: &TaskList) -> Vec<u64> {
+        list.tasks.iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn moving_skips_archived_tasks_and_stops_at_the_ends() {
+        let mut list = TaskList {
+            tasks: vec![task(0, "A"), task(1, "B"), task(2, "C")],
+            active_task: None,
+        };
+        list.tasks[1].archived = true;
+
+        list.move_task(2, -1);
+        assert_eq!(ids(&list), [2, 1, 0]);
+        list.move_task(2, -1);
+        assert_eq!(ids(&list), [2, 1, 0]);
+        list.move_task(1, 1);
+        assert_eq!(ids(&list), [2, 1, 0]);
+    }
+
+    #[test]
+    fn imports_keep_the_counts_of_known_tasks() {
+        let mut list = TaskList {
+            tasks: vec![task(3, "Own")],
+            active_task: None,
+        };
+        list.merge_imported(vec![
+            source_task("Email", false, 0),
+            source_task("Email", false, 1),
+        ]);
+        assert_eq!(ids(&list), [3, 4, 5]);
+        assert!(list.tasks[1].imported);
+
+        list.tasks[2].actual_pomodoros = 2;
+        list.active_task = Some(4);
+        // tasks with the same name are matched by their order, so the second one is dropped
+        list.merge_imported(vec![source_task("Email", false, 0)]);
+        assert_eq!(ids(&list), [3, 4]);
+        assert_eq!(list.tasks[1].actual_pomodoros, 0);
+        assert_eq!(list.active_task, Some(4));
+    }
+
+    #[test]
+    fn a_finished_task_is_no_longer_active() {
+        let mut list = TaskList {
+            tasks: vec![task(0, "Own")],
+            active_task: None,
+        };
+        list.merge_imported(vec![source_task("Email", false, 0)]);
+        list.active_task = Some(1);
+        assert_eq!(list.active().map(|task| task.name.as_str()), Some("Email"));
+
+        list.merge_imported(vec![source_task("Email", true, 0)]);
+        assert_eq!(list.active_task, None);
+
+        list.active_task = Some(0);
+        list.task_mut(0).unwrap().archived = true;
+        list.deactivate_if_done();
+        assert_eq!(list.active(), None);
+    }
+}
@@ -0,0 +1,871 @@
+use chrono::{DateTime, Local};
+use tokio::time::Instant;
+
+use crate::{
+    bus::SessionEventKind,
+    cycle::Cycle,
+    history::{SessionKind, SessionOutcome, SessionRecord},
+    interruptions::Interruption,
+    settings::{Phase, Settings},
+    undo::UndoStack,
+};
+
+/// Undo and redo forget the oldest actions beyond this
+pub const UNDO_LIMIT: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UndoAction {
+    Skip,
+    Restart,
+    Start,
+    Snooze,
+}
+
+impl UndoAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UndoAction::Skip => "skip",
+            UndoAction::Restart => "restart",
+            UndoAction::Start => "start",
+            UndoAction::Snooze => "snooze",
+        }
+    }
+}
+
+/// What can happen to the timer, see `TimerModel::apply`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimerAction {
+    /// Starts the current phase from the beginning
+    Start,
+    /// Starts the sequence over at its first focus phase, a paused session is resumed instead
+    StartFirstFocus,
+    /// Starts or resumes the timer, or pauses it unless a strict break is running
+    Toggle,
+    /// Pauses the running session, also a strict break. `idle_millis` is the time the user was away,
+    /// given back to a focus session.
+    Pause {
+        idle_millis: u32,
+    },
+    /// Ends the session early, a flowtime focus session counts as completed
+    Skip,
+    /// Ends a strict break early
+    EmergencySkip,
+    /// The countdown reached zero
+    Expire,
+    /// Starts the countdown of the current session over
+    Restart,
+    /// Brings the expiry back at the given time instead of moving on right away
+    Snooze(Instant),
+    /// The snooze set for the given time is over
+    SnoozeOver(Instant),
+    /// Goes back to the stopped timer of the next session after an expiry
+    Dismiss,
+    /// Starts the sequence and its counts over for a new day, unless a session is in progress
+    NewDay,
+    /// Pauses the running focus session and runs the break after it on its own, e.g. while the screen is locked.
+    /// Other actions wait until `Unlock`.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    LockBreak,
+    /// Ends the break of `LockBreak` and brings back the paused focus session as it was
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    Unlock,
+    Undo,
+    Redo,
+}
+
+/// How far the timer is, everything an undo or redo puts back
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    pub cycle: Cycle,
+    pub millis_remaining: u32,
+    /// Time counted up in a flowtime focus session
+    pub millis_elapsed: u32,
+    /// Length of the break earned in the last flowtime focus session, used instead of the phase duration
+    pub flow_break: Option<u32>,
+    pub running: bool,
+    pub expired: bool,
+    /// When the current session was first started, for the session history
+    pub session_started_at: Option<DateTime<Local>>,
+    /// When a snoozed expiry comes back
+    pub snoozed_until: Option<Instant>,
+    /// Interruptions of the current focus session, most recent last
+    pub interruptions: Vec<Interruption>,
+    /// The focus session paused by `TimerAction::LockBreak`, the timer shows the break meanwhile
+    pub locked_focus: Option<Box<Progress>>,
+}
+
+impl Progress {
+    /// The first phase of the sequence, not started yet
+    #[cfg(test)]
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            cycle: Cycle::default(),
+            millis_remaining: settings.session_duration(0),
+            millis_elapsed: 0,
+            flow_break: None,
+            running: false,
+            expired: false,
+            session_started_at: None,
+            snoozed_until: None,
+            interruptions: Vec::new(),
+            locked_focus: None,
+        }
+    }
+
+    pub fn phase(&self, settings: &Settings) -> Phase {
+        settings.phase(self.cycle.index)
+    }
+
+    /// Whether the timer counts up because the focus session runs in flowtime
+    pub fn counting_up(&self, settings: &Settings) -> bool {
+        settings.flowtime && self.phase(settings).is_focus()
+    }
+
+    /// Duration of the current phase in milliseconds, breaks after flowtime sessions last as long as they earned
+    pub fn phase_duration(&self, settings: &Settings) -> u32 {
+        let phase = self.phase(settings);
+        match self.flow_break {
+            Some(flow_break) if !phase.is_focus() => flow_break,
+            _ => phase.duration(),
+        }
+    }
+
+    /// Whether a running break can't be paused, restarted or skipped because strict breaks are on
+    pub fn break_enforced(&self, settings: &Settings) -> bool {
+        settings.strict_breaks
+            && !self.phase(settings).is_focus()
+            && self.running
+            && self.locked_focus.is_none()
+    }
+
+    /// Whether a session was started and not finished yet, running or paused
+    pub fn in_progress(&self) -> bool {
+        self.session_started_at.is_some()
+    }
+
+    /// Takes `millis` off the time spent in the session, no further back than its start
+    fn give_back(&mut self, millis: u32, settings: &Settings) {
+        if self.counting_up(settings) {
+            self.millis_elapsed = self.millis_elapsed.saturating_sub(millis);
+        } else {
+            self.millis_remaining = self
+                .millis_remaining
+                .saturating_add(millis)
+                .min(self.phase_duration(settings));
+        }
+    }
+
+    /// Stops the timer at the start of the current phase
+    fn clear(&mut self, settings: &Settings) {
+        self.running = false;
+        self.millis_remaining = self.phase_duration(settings);
+        self.millis_elapsed = 0;
+        self.expired = false;
+        self.session_started_at = None;
+        self.interruptions.clear();
+    }
+
+    /// Moves on to the next phase of the sequence, counting finished focus sessions and sequences
+    fn advance(&mut self, settings: &Settings) {
+        self.cycle = self.cycle.advance(&settings.phases());
+        if self.phase(settings).is_focus() {
+            self.flow_break = None;
+        }
+    }
+}
+
+/// Length of the break after a flowtime focus session of `elapsed` milliseconds,
+/// at least a minute so short sessions still get a real break
+fn earned_break(elapsed: u32, settings: &Settings) -> u32 {
+    (elapsed / 100 * settings.flowtime_break_percent).max(60 * 1000)
+}
+
+/// Carries out what the timer's actions cause outside of it
+pub trait TimerEffects {
+    /// Something happened to the session, `progress` is the timer right when it did
+    fn emit(&mut self, kind: SessionEventKind, progress: &Progress);
+    /// Adds a finished or skipped session to the history and returns it as saved
+    fn record(
+        &mut self,
+        kind: SessionKind,
+        started_at: DateTime<Local>,
+        duration_secs: u32,
+        outcome: SessionOutcome,
+        interruptions: Vec<Interruption>,
+    ) -> SessionRecord;
+    /// Puts a record back when a skip is redone and returns it as saved
+    fn restore_record(&mut self, record: SessionRecord) -> SessionRecord;
+    fn remove_record(&mut self, record: &SessionRecord);
+    /// A session ran out or a snooze is over, `alert` unless the next session started on its own
+    fn ring(&mut self, alert: bool);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndoEntry {
+    pub action: UndoAction,
+    /// The timer on the other side of the action
+    progress: Progress,
+    /// History record the action added
+    record: Option<SessionRecord>,
+}
+
+impl UndoEntry {
+    #[cfg(test)]
+    pub fn record(&self) -> Option<&SessionRecord> {
+        self.record.as_ref()
+    }
+}
+
+/// The timer without the GlobalSignals it's shown through, every change goes through `apply`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimerModel {
+    pub progress: Progress,
+    pub undo: UndoStack<UndoEntry>,
+}
+
+impl TimerModel {
+    #[cfg(test)]
+    pub fn new(progress: Progress) -> Self {
+        Self {
+            progress,
+            undo: UndoStack::new(UNDO_LIMIT),
+        }
+    }
+
+    /// Applies `action` as it happens at `now`
+    pub fn apply(
+        &mut self,
+        action: TimerAction,
+        settings: &Settings,
+        now: DateTime<Local>,
+        effects: &mut impl TimerEffects,
+    ) {
+        if self.progress.locked_focus.is_some()
+            && !matches!(action, TimerAction::Expire | TimerAction::Unlock)
+        {
+            return;
+        }
+
+        match action {
+            TimerAction::Start => self.start(settings, now, effects),
+            TimerAction::StartFirstFocus => {
+                if self.progress.running {
+                    return;
+                }
+                if self.progress.in_progress() {
+                    self.toggle(settings, now, effects);
+                    return;
+                }
+
+                self.push_undo(UndoAction::Start);
+                let phases = settings.phases();
+                self.progress.cycle.index = phases.iter().position(Phase::is_focus).unwrap_or(0);
+                self.progress.flow_break = None;
+                self.begin(settings, now, effects);
+            }
+            TimerAction::Toggle => self.toggle(settings, now, effects),
+            TimerAction::Pause { idle_millis } => {
+                if self.progress.running && self.progress.phase(settings).is_focus() {
+                    self.progress.give_back(idle_millis, settings);
+                }
+                self.pause(effects);
+            }
+            TimerAction::Skip => {
+                if self.progress.break_enforced(settings) {
+                    return;
+                }
+                // a flowtime session is over when the user says so
+                if self.progress.counting_up(settings) && self.progress.in_progress() {
+                    self.complete(false, settings, now, effects);
+                } else {
+                    self.skip(SessionOutcome::Skipped, settings, effects);
+                }
+            }
+            TimerAction::EmergencySkip => {
+                if self.progress.break_enforced(settings) {
+                    self.skip(SessionOutcome::EmergencySkipped, settings, effects);
+                }
+            }
+            TimerAction::Expire if self.progress.locked_focus.is_some() => {
+                if !self.progress.running {
+                    return;
+                }
+                // the break is over, the focus session still waits for the unlock
+                self.progress.running = false;
+                self.progress.millis_remaining = 0;
+                effects.emit(SessionEventKind::BreakEnd, &self.progress);
+                self.finish(SessionOutcome::Completed, settings, effects);
+                self.progress.session_started_at = None;
+            }
+            TimerAction::Expire => {
+                if self.progress.running && !self.progress.counting_up(settings) {
+                    self.progress.millis_remaining = 0;
+                    self.complete(true, settings, now, effects);
+                }
+            }
+            TimerAction::Restart => {
+                // a strict break only ends early through the emergency skip
+                if self.progress.break_enforced(settings) {
+                    return;
+                }
+                // a session that never started has nothing to take back
+                if self.progress.in_progress() {
+                    self.push_undo(UndoAction::Restart);
+                }
+                self.progress.clear(settings);
+            }
+            TimerAction::Snooze(until) => {
+                if !self.progress.expired {
+                    return;
+                }
+                self.push_undo(UndoAction::Snooze);
+                self.progress.snoozed_until = Some(until);
+                self.progress.expired = false;
+            }
+            TimerAction::SnoozeOver(until) => {
+                // undone, or snoozed again in the meantime
+                if self.progress.snoozed_until != Some(until) {
+                    return;
+                }
+                self.progress.snoozed_until = None;
+                if !self.progress.running && !self.progress.in_progress() {
+                    self.progress.expired = true;
+                    effects.ring(true);
+                }
+            }
+            TimerAction::Dismiss => {
+                if self.progress.expired {
+                    self.progress.clear(settings);
+                }
+            }
+            TimerAction::NewDay => {
+                if self.progress.in_progress() {
+                    return;
+                }
+                // undoing would bring back yesterday's counts
+                self.undo.clear();
+                self.progress.cycle = Cycle::default();
+                self.progress.flow_break = None;
+                self.progress.snoozed_until = None;
+                self.progress.clear(settings);
+            }
+            TimerAction::LockBreak => self.lock_break(settings, now, effects),
+            TimerAction::Unlock => {
+                let Some(focus) = self.progress.locked_focus.take() else {
+                    return;
+                };
+                if self.progress.running {
+                    effects.emit(SessionEventKind::BreakEnd, &self.progress);
+                    // a break cut short isn't recorded, the focus session it interrupted goes on
+                }
+                self.progress = *focus;
+            }
+            TimerAction::Undo => self.undo(settings, effects),
+            TimerAction::Redo => self.redo(effects),
+        }
+    }
+
+    /// Remembers the timer before `action` changes it, a new action can't be redone after
+    fn push_undo(&mut self, action: UndoAction) {
+        self.undo.push(UndoEntry {
+            action,
+            progress: self.progress.clone(),
+            record: None,
+        });
+    }
+
+    fn start(
+        &mut self,
+        settings: &Settings,
+        now: DateTime<Local>,
+        effects: &mut impl TimerEffects,
+    ) {
+        self.push_undo(UndoAction::Start);
+        self.begin(settings, now, effects);
+    }
+
+    fn begin(
+        &mut self,
+        settings: &Settings,
+        now: DateTime<Local>,
+        effects: &mut impl TimerEffects,
+    ) {
+        self.progress.clear(settings);
+        self.progress.snoozed_until = None;
+        self.progress.session_started_at = Some(now);
+        self.progress.running = true;
+
+        let kind = SessionEventKind::started(self.progress.phase(settings).is_focus());
+        effects.emit(kind, &self.progress);
+    }
+
+    fn toggle(
+        &mut self,
+        settings: &Settings,
+        now: DateTime<Local>,
+        effects: &mut impl TimerEffects,
+    ) {
+        if self.progress.running {
+            if !self.progress.break_enforced(settings) {
+                self.pause(effects);
+            }
+            return;
+        }
+
+        let kind = if self.progress.in_progress() {
+            SessionEventKind::Resume
+        } else {
+            self.push_undo(UndoAction::Start);
+            // starting the next session is the end of the expiry
+            self.progress.expired = false;
+            self.progress.snoozed_until = None;
+            self.progress.session_started_at = Some(now);
+            SessionEventKind::started(self.progress.phase(settings).is_focus())
+        };
+        self.progress.running = true;
+        effects.emit(kind, &self.progress);
+    }
+
+    fn pause(&mut self, effects: &mut impl TimerEffects) {
+        if self.progress.running {
+            self.progress.running = false;
+            effects.emit(SessionEventKind::Pause, &self.progress);
+        }
+    }
+
+    /// Records the current session in the history if it was ever started
+    fn finish(
+        &mut self,
+        outcome: SessionOutcome,
+        settings: &Settings,
+        effects: &mut impl TimerEffects,
+    ) -> Option<SessionRecord> {
+        let progress = &mut self.progress;
+        let started_at = progress.session_started_at?;
+
+        let elapsed = if progress.counting_up(settings) {
+            progress.millis_elapsed
+        } else {
+            progress
+                .phase_duration(settings)
+                .saturating_sub(progress.millis_remaining)
+        };
+        Some(effects.record(
+            progress.phase(settings).kind,
+            started_at,
+            elapsed / 1000,
+            outcome,
+            std::mem::take(&mut progress.interruptions),
+        ))
+    }
+
+    /// Ends the session as completed and moves on to the next phase, `ran_out` when the countdown reached zero
+    fn complete(
+        &mut self,
+        ran_out: bool,
+        settings: &Settings,
+        now: DateTime<Local>,
+        effects: &mut impl TimerEffects,
+    ) {
+        self.progress.expired = true;
+        self.progress.running = false;
+        // earlier actions can't be undone once the session is in the history after them
+        self.undo.clear();
+
+        let is_focus = self.progress.phase(settings).is_focus();
+        effects.emit(SessionEventKind::ended(is_focus), &self.progress);
+        let flow_elapsed = self
+            .progress
+            .counting_up(settings)
+            .then_some(self.progress.millis_elapsed);
+        self.finish(SessionOutcome::Completed, settings, effects);
+        // the next session only counts as started once it's started
+        self.progress.session_started_at = None;
+        self.progress.millis_elapsed = 0;
+
+        self.progress.advance(settings);
+        let next_phase = self.progress.phase(settings);
+        if let Some(elapsed) = flow_elapsed.filter(|_| !next_phase.is_focus()) {
+            self.progress.flow_break = Some(earned_break(elapsed, settings));
+        }
+        self.progress.millis_remaining = self.progress.phase_duration(settings);
+
+        // outside working hours the cycle waits for the next block instead of starting on its own
+        let auto_start = settings.auto_start(&next_phase) && settings.is_working_time(now);
+        if ran_out {
+            effects.ring(!auto_start);
+        }
+        if auto_start {
+            self.start(settings, now, effects);
+        }
+    }
+
+    fn skip(
+        &mut self,
+        outcome: SessionOutcome,
+        settings: &Settings,
+        effects: &mut impl TimerEffects,
+    ) {
+        self.push_undo(UndoAction::Skip);
+
+        effects.emit(SessionEventKind::Skip, &self.progress);
+        let record = self.finish(outcome, settings, effects);
+        if let Some(entry) = self.undo.last_mut() {
+            entry.record = record;
+        }
+
+        self.progress.advance(settings);
+        self.progress.clear(settings);
+    }
+
+    fn lock_break(
+        &mut self,
+        settings: &Settings,
+        now: DateTime<Local>,
+        effects: &mut impl TimerEffects,
+    ) {
+        if !self.progress.running || !self.progress.phase(settings).is_focus() {
+            return;
+        }
+        let phases = settings.phases();
+        let next = (self.progress.cycle.index + 1) % phases.len();
+        if phases[next].is_focus() {
+            return;
+        }
+
+        self.pause(effects);
+        let focus = self.progress.clone();
+        let flow_break = self
+            .progress
+            .counting_up(settings)
+            .then(|| earned_break(self.progress.millis_elapsed, settings));
+
+        // only the index moves, the counters stay with the focus session
+        self.progress.cycle.index = next;
+        self.progress.flow_break = flow_break;
+        self.progress.clear(settings);
+        self.progress.snoozed_until = None;
+        self.progress.locked_focus = Some(Box::new(focus));
+        self.progress.session_started_at = Some(now);
+        self.progress.running = true;
+        effects.emit(SessionEventKind::BreakStart, &self.progress);
+    }
+
+    /// Takes back the most recent skip, restart, start or snooze
+    fn undo(&mut self, settings: &Settings, effects: &mut impl TimerEffects) {
+        // a strict break can't be escaped by undoing its start
+        if self.progress.break_enforced(settings) {
+            return;
+        }
+        let progress = &mut self.progress;
+        self.undo.undo(|entry| {
+            // the skipped session continues, so it shouldn't stay in the history
+            if let Some(record) = &entry.record {
+                effects.remove_record(record);
+            }
+            UndoEntry {
+                progress: std::mem::replace(progress, entry.progress),
+                ..entry
+            }
+        });
+    }
+
+    /// Does the most recently undone action again
+    fn redo(&mut self, effects: &mut impl TimerEffects) {
+        // nothing is left to redo once a strict break starts, unless an undo got back into it
+        let progress = &mut self.progress;
+        self.undo.redo(|entry| UndoEntry {
+            progress: std::mem::replace(progress, entry.progress),
+            // the next undo has to remove the record under the id it gets now
+            record: entry.record.map(|record| effects.restore_record(record)),
+            ..entry
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Keeps the history and the events in memory
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Recorder {
+        history: Vec<SessionRecord>,
+        next_id: u64,
+        events: Vec<SessionEventKind>,
+    }
+
+    impl TimerEffects for Recorder {
+        fn emit(&mut self, kind: SessionEventKind, _progress: &Progress) {
+            self.events.push(kind);
+        }
+
+        fn record(
+            &mut self,
+            kind: SessionKind,
+            started_at: DateTime<Local>,
+            duration_secs: u32,
+            outcome: SessionOutcome,
+            interruptions: Vec<Interruption>,
+        ) -> SessionRecord {
+            self.next_id += 1;
+            let record = SessionRecord {
+                id: self.next_id,
+                kind,
+                started_at,
+                ended_at: started_at,
+                duration_secs,
+                completed: outcome == SessionOutcome::Completed,
+                emergency_skip: outcome == SessionOutcome::EmergencySkipped,
+                task: None,
+                preset: None,
+                manual: false,
+                interruptions,
+            };
+            self.history.push(record.clone());
+            record
+        }
+
+        fn restore_record(&mut self, record: SessionRecord) -> SessionRecord {
+            if !self.history.contains(&record) {
+                self.history.push(record.clone());
+            }
+            record
+        }
+
+        fn remove_record(&mut self, record: &SessionRecord) {
+            self.history.retain(|r| r.id != record.id);
+        }
+
+        fn ring(&mut self, _alert: bool) {}
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    enum Op {
+        Start,
+        Pause,
+        Toggle,
+        Skip,
+        Expire,
+        Undo,
+        Redo,
+    }
+
+    fn any_op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            Just(Op::Start),
+            Just(Op::Pause),
+            Just(Op::Toggle),
+            Just(Op::Skip),
+            Just(Op::Expire),
+            Just(Op::Undo),
+            Just(Op::Redo),
+        ]
+    }
+
+    fn any_settings() -> impl Strategy<Value = Settings> {
+        (1u32..6, any::<bool>(), any::<bool>(), any::<bool>()).prop_map(
+            |(long_break_interval, flowtime, auto_start_breaks, strict_breaks)| Settings {
+                long_break_interval,
+                flowtime,
+                auto_start_breaks,
+                strict_breaks,
+                ..Settings::default()
+            },
+        )
+    }
+
+    /// The counters follow from where in the sequence the timer is
+    fn check_counts(model: &TimerModel, settings: &Settings) -> Result<(), TestCaseError> {
+        let phases = settings.phases();
+        let cycle = model.progress.cycle;
+        let focus_per_cycle = phases.iter().filter(|phase| phase.is_focus()).count() as u32;
+        let focus_before = phases[..cycle.index]
+            .iter()
+            .filter(|phase| phase.is_focus())
+            .count() as u32;
+
+        prop_assert!(cycle.index < phases.len());
+        prop_assert_eq!(
+            cycle.sessions,
+            cycle.cycles * focus_per_cycle + focus_before
+        );
+        Ok(())
+    }
+
+    /// Every skip that can be undone has its record in the history, every one that was undone doesn't
+    fn check_records(model: &TimerModel, recorder: &Recorder) -> Result<(), TestCaseError> {
+        let (undo, redo) = model.undo.entries();
+        for entry in undo {
+            if let Some(record) = entry.record() {
+                prop_assert!(recorder.history.contains(record));
+            }
+        }
+        for entry in redo {
+            if let Some(record) = entry.record() {
+                prop_assert!(recorder.history.iter().all(|r| r.id != record.id));
+            }
+        }
+
+        let mut ids: Vec<u64> = recorder.history.iter().map(|r| r.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        prop_assert_eq!(ids.len(), recorder.history.len());
+        Ok(())
+    }
+
+    #[test]
+    fn skipping_a_started_session_records_it_until_undone() {
+        let settings = Settings::default();
+        let start = Local::now();
+        let mut model = TimerModel::new(Progress::new(&settings));
+        let mut recorder = Recorder::default();
+
+        model.apply(TimerAction::Start, &settings, start, &mut recorder);
+        model.progress.millis_remaining -= 60 * 1000;
+        let before_skip = model.clone();
+        let later = start + TimeDelta::minutes(1);
+        model.apply(TimerAction::Skip, &settings, later, &mut recorder);
+
+        assert_eq!(model.progress.cycle.sessions, 1);
+        assert_eq!(recorder.history.len(), 1);
+        assert!(!recorder.history[0].completed);
+        assert_eq!(recorder.history[0].duration_secs, 60);
+
+        model.apply(TimerAction::Undo, &settings, later, &mut recorder);
+        assert_eq!(model.progress, before_skip.progress);
+        assert!(recorder.history.is_empty());
+    }
+
+    #[test]
+    fn a_new_day_starts_the_counts_over_between_sessions() {
+        let settings = Settings::default();
+        let now = Local::now();
+        let mut model = TimerModel::new(Progress::new(&settings));
+        let mut recorder = Recorder::default();
+
+        for _ in 0..3 {
+            model.apply(TimerAction::Skip, &settings, now, &mut recorder);
+        }
+        model.apply(TimerAction::Start, &settings, now, &mut recorder);
+        model.apply(TimerAction::NewDay, &settings, now, &mut recorder);
+        assert_eq!(model.progress.cycle.sessions, 2);
+
+        model.apply(TimerAction::Skip, &settings, now, &mut recorder);
+        model.apply(TimerAction::NewDay, &settings, now, &mut recorder);
+        assert_eq!(model.progress, Progress::new(&settings));
+        assert_eq!(model.undo, UndoStack::new(UNDO_LIMIT));
+    }
+
+    #[test]
+    fn a_lock_break_keeps_the_focus_session_for_the_unlock() {
+        let settings = Settings::default();
+        let start = Local::now();
+        let mut model = TimerModel::new(Progress::new(&settings));
+        let mut recorder = Recorder::default();
+
+        model.apply(TimerAction::Start, &settings, start, &mut recorder);
+        model.progress.millis_remaining -= 10 * 60 * 1000;
+        let mut focus = model.progress.clone();
+        focus.running = false;
+
+        let locked = start + TimeDelta::minutes(10);
+        model.apply(TimerAction::LockBreak, &settings, locked, &mut recorder);
+        assert!(model.progress.running);
+        assert!(!model.progress.phase(&settings).is_focus());
+        assert_eq!(model.progress.millis_remaining, 5 * 60 * 1000);
+        assert_eq!(model.progress.cycle.sessions, 0);
+        assert!(!model.progress.break_enforced(&Settings {
+            strict_breaks: true,
+            ..settings.clone()
+        }));
+
+        // nothing else happens to the timer while the screen is locked
+        model.apply(TimerAction::Skip, &settings, locked, &mut recorder);
+        assert_eq!(model.progress.locked_focus.as_deref(), Some(&focus));
+
+        model.apply(TimerAction::Unlock, &settings, locked, &mut recorder);
+        assert_eq!(model.progress, focus);
+        assert_eq!(model.progress.millis_remaining, 15 * 60 * 1000);
+        assert!(recorder.history.is_empty());
+        assert_eq!(
+            recorder.events,
+            [
+                SessionEventKind::FocusStart,
+                SessionEventKind::Pause,
+                SessionEventKind::BreakStart,
+                SessionEventKind::BreakEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn a_lock_break_that_runs_out_is_recorded_as_a_break() {
+        let settings = Settings::default();
+        let start = Local::now();
+        let mut model = TimerModel::new(Progress::new(&settings));
+        let mut recorder = Recorder::default();
+
+        model.apply(TimerAction::Start, &settings, start, &mut recorder);
+        model.apply(TimerAction::LockBreak, &settings, start, &mut recorder);
+        model.apply(TimerAction::Expire, &settings, start, &mut recorder);
+        assert!(!model.progress.running);
+        assert!(!model.progress.expired);
+
+        model.apply(TimerAction::Unlock, &settings, start, &mut recorder);
+        assert!(model.progress.phase(&settings).is_focus());
+        assert_eq!(model.progress.cycle.sessions, 0);
+        assert_eq!(model.progress.session_started_at, Some(start));
+        assert_eq!(recorder.history.len(), 1);
+        assert_eq!(recorder.history[0].kind, SessionKind::ShortBreak);
+        assert!(recorder.history[0].completed);
+    }
+
+    proptest! {
+        #[test]
+        fn random_commands_keep_the_timer_consistent(
+            settings in any_settings(),
+            ops in prop::collection::vec(any_op(), 0..200),
+        ) {
+            let mut now = Local::now();
+            let mut model = TimerModel::new(Progress::new(&settings));
+            let mut recorder = Recorder::default();
+
+            for op in ops {
+                now += TimeDelta::seconds(30);
+                let action = match op {
+                    Op::Start => TimerAction::Start,
+                    Op::Pause => TimerAction::Pause { idle_millis: 0 },
+                    Op::Toggle => TimerAction::Toggle,
+                    Op::Skip => TimerAction::Skip,
+                    Op::Expire => TimerAction::Expire,
+                    Op::Undo => TimerAction::Undo,
+                    Op::Redo => TimerAction::Redo,
+                };
+
+                if let Op::Undo = op {
+                    let before = (model.clone(), recorder.clone());
+                    model.apply(TimerAction::Undo, &settings, now, &mut recorder);
+                    if model.undo.entries().1.len() > before.0.undo.entries().1.len() {
+                        // redoing right after an undo gets back to exactly where the timer was
+                        let mut redone = (model.clone(), recorder.clone());
+                        redone.0.apply(TimerAction::Redo, &settings, now, &mut redone.1);
+                        prop_assert_eq!(&redone.0, &before.0);
+                        prop_assert_eq!(&redone.1.history, &before.1.history);
+                    }
+                } else {
+                    model.apply(action, &settings, now, &mut recorder);
+                }
+
+                check_counts(&model, &settings)?;
+                check_records(&model, &recorder)?;
+                if model.progress.expired {
+                    prop_assert!(!model.progress.running);
+                }
+            }
+        }
+    }
+}
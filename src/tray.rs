@@ -1,76 +1,85 @@
-use std::{
-    sync::{
-        mpsc::{Receiver, Sender},
-        Mutex,
-    },
-    thread,
-};
+use std::sync::Mutex;
 
-use dioxus::desktop::window;
+use dioxus::{desktop::window, prelude::*};
 use tracing::error;
-use tray_icon::{TrayIconBuilder, TrayIconEvent};
+use tray_icon::{
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
+    TrayIconBuilder, TrayIconEvent,
+};
 
-#[derive(Debug)]
-pub enum WindowCommand {
-    Show,
-}
+use crate::{
+    bus::{send_command, SettingsCommand, TimerCommand, WindowCommand},
+    components::{
+        settings_panel::open_settings,
+        timer::{redo_action, undo_action},
+    },
+    interruptions::{can_log_interruption, InterruptionKind},
+    reminders::pending_reminders,
+    settings::SETTINGS,
+    timer_model::UndoAction,
+};
 
-pub static TRAY_EVENT_SENDER: Mutex<Option<Sender<TrayIconEvent>>> = Mutex::new(None);
-pub static TRAY_EVENT_RECEIVER: Mutex<Option<Receiver<TrayIconEvent>>> = Mutex::new(None);
-pub static WINDOW_COMMAND_SENDER: Mutex<Option<Sender<WindowCommand>>> = Mutex::new(None);
-pub static WINDOW_COMMAND_RECEIVER: Mutex<Option<Receiver<WindowCommand>>> = Mutex::new(None);
+const MENU_SHOW_ID: &str = "show";
+const MENU_SETTINGS_ID: &str = "settings";
+const MENU_UNDO_ID: &str = "undo";
+const MENU_REDO_ID: &str = "redo";
+const MENU_INTERNAL_INTERRUPTION_ID: &str = "interruption:internal";
+const MENU_EXTERNAL_INTERRUPTION_ID: &str = "interruption:external";
+// followed by the preset name
+const MENU_PRESET_PREFIX: &str = "preset:";
 
 thread_local! {
     static TRAY_ICON: Mutex<Option<tray_icon::TrayIcon>> = const { Mutex::new(None) };
 }
 
 pub fn init_tray_handler() {
-    tray_icon::TrayIconEvent::set_event_handler(Some(move |event| {
-        if let Some(sender) = TRAY_EVENT_SENDER.lock().unwrap().as_ref() {
-            let _ = sender.send(event);
+    TrayIconEvent::set_event_handler(Some(move |event| {
+        if let TrayIconEvent::Click {
+            button: tray_icon::MouseButton::Left,
+            button_state: tray_icon::MouseButtonState::Up,
+            ..
+        } = event
+        {
+            send_command(WindowCommand::Show);
         }
     }));
-}
 
-pub fn init_tray_listener() {
-    thread::spawn(move || loop {
-        if let Some(receiver) = TRAY_EVENT_RECEIVER.lock().unwrap().as_ref() {
-            match receiver.recv() {
-                Ok(tray_event) => {
-                    if let TrayIconEvent::Click {
-                        button: tray_icon::MouseButton::Left,
-                        button_state: tray_icon::MouseButtonState::Up,
-                        ..
-                    } = tray_event
-                    {
-                        if let Some(sender) = WINDOW_COMMAND_SENDER.lock().unwrap().as_ref() {
-                            let _ = sender.send(WindowCommand::Show);
-                        }
-                    }
-                }
-                Err(_) => {
-                    error!("System tray receiver disconnected.");
-                    break;
-                }
+    MenuEvent::set_event_handler(Some(move |event: MenuEvent| match event.id.as_ref() {
+        MENU_SHOW_ID => send_command(WindowCommand::Show),
+        MENU_SETTINGS_ID => send_command(WindowCommand::ShowSettings),
+        MENU_UNDO_ID => send_command(TimerCommand::Undo),
+        MENU_REDO_ID => send_command(TimerCommand::Redo),
+        MENU_INTERNAL_INTERRUPTION_ID => {
+            send_command(TimerCommand::Interrupt(Some(InterruptionKind::Internal)))
+        }
+        MENU_EXTERNAL_INTERRUPTION_ID => {
+            send_command(TimerCommand::Interrupt(Some(InterruptionKind::External)))
+        }
+        id => {
+            if let Some(name) = id.strip_prefix(MENU_PRESET_PREFIX) {
+                send_command(SettingsCommand::SwitchPreset(name.to_string()));
             }
         }
-    });
+    }));
 }
 
-pub fn handle_window_commands() {
-    if let Some(receiver) = WINDOW_COMMAND_RECEIVER.lock().unwrap().as_ref() {
-        while let Ok(command) = receiver.try_recv() {
-            let handle = window();
-            match command {
-                WindowCommand::Show => {
-                    handle.set_visible(true);
-                }
-            }
+pub fn handle_window_command(command: WindowCommand) {
+    let handle = window();
+    match command {
+        WindowCommand::Show => {
+            handle.set_visible(true);
+        }
+        WindowCommand::ShowSettings => {
+            handle.set_visible(true);
+            handle.set_focus();
+            open_settings();
         }
     }
 }
 
+#[cfg(target_os = "macos")]
 use std::ffi::c_void;
+#[cfg(target_os = "macos")]
 extern "C" {
     fn CFRunLoopGetMain() -> *mut c_void;
     fn CFRunLoopWakeUp(rl: *mut c_void);
@@ -84,16 +93,144 @@ pub fn set_tray_title(new_title: &str) {
     });
 }
 
+pub fn set_tray_tooltip(new_tooltip: &str) {
+    TRAY_ICON.with(|tray| {
+        if let Some(tray_icon) = tray.lock().unwrap().as_ref() {
+            if let Err(e) = tray_icon.set_tooltip(Some(new_tooltip)) {
+                error!("Failed to set tray tooltip: {}", e);
+            }
+        }
+    });
+}
+
+/// `presets` are the names of all presets, `active` the one in use if any,
+/// `reminders` are listed for information only, `undo` and `redo` name the actions they would take back or repeat,
+/// interruptions can only be logged while `focus_running`
+fn build_menu(
+    presets: &[String],
+    active: Option<&str>,
+    reminders: &[String],
+    undo: Option<UndoAction>,
+    redo: Option<UndoAction>,
+    focus_running: bool,
+) -> Menu {
+    let preset_items: Vec<CheckMenuItem> = presets
+        .iter()
+        .map(|name| {
+            CheckMenuItem::with_id(
+                format!("{}{}", MENU_PRESET_PREFIX, name),
+                name,
+                true,
+                active == Some(name.as_str()),
+                None,
+            )
+        })
+        .collect();
+    let preset_refs: Vec<&dyn IsMenuItem> = preset_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem)
+        .collect();
+
+    let menu = Menu::new();
+    let _ = menu.append_items(&[
+        &MenuItem::with_id(MENU_SHOW_ID, "Show AuraTimer", true, None),
+        &MenuItem::with_id(MENU_SETTINGS_ID, "Settings…", true, None),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id(
+            MENU_UNDO_ID,
+            undo.map_or("Undo".to_string(), |action| {
+                format!("Undo {}", action.name())
+            }),
+            undo.is_some(),
+            None,
+        ),
+        &MenuItem::with_id(
+            MENU_REDO_ID,
+            redo.map_or("Redo".to_string(), |action| {
+                format!("Redo {}", action.name())
+            }),
+            redo.is_some(),
+            None,
+        ),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id(
+            MENU_INTERNAL_INTERRUPTION_ID,
+            "Log internal interruption",
+            focus_running,
+            None,
+        ),
+        &MenuItem::with_id(
+            MENU_EXTERNAL_INTERRUPTION_ID,
+            "Log external interruption",
+            focus_running,
+            None,
+        ),
+    ]);
+    if !presets.is_empty() {
+        match Submenu::with_items("Presets", true, &preset_refs) {
+            Ok(submenu) => {
+                let _ = menu.append(&submenu);
+            }
+            Err(e) => error!("Failed to create presets menu: {}", e),
+        }
+    }
+    if !reminders.is_empty() {
+        let reminder_items: Vec<MenuItem> = reminders
+            .iter()
+            .map(|reminder| MenuItem::new(reminder, false, None))
+            .collect();
+        let reminder_refs: Vec<&dyn IsMenuItem> = reminder_items
+            .iter()
+            .map(|item| item as &dyn IsMenuItem)
+            .collect();
+        match Submenu::with_items("Reminders", true, &reminder_refs) {
+            Ok(submenu) => {
+                let _ = menu.append(&submenu);
+            }
+            Err(e) => error!("Failed to create reminders menu: {}", e),
+        }
+    }
+    let _ = menu.append_items(&[
+        &PredefinedMenuItem::separator(),
+        &PredefinedMenuItem::quit(None),
+    ]);
+    menu
+}
+
+/// Rebuilds the tray menu when presets are added, removed or switched, when reminders change,
+/// after undoable actions and when a focus session starts or stops
+pub fn use_tray_menu() {
+    use_effect(|| {
+        let settings = SETTINGS.read();
+        let presets: Vec<String> = settings.presets.iter().map(|p| p.name.clone()).collect();
+        let active = settings.current_preset().map(|p| p.name.as_str());
+        let menu = build_menu(
+            &presets,
+            active,
+            &pending_reminders(),
+            undo_action(),
+            redo_action(),
+            can_log_interruption(),
+        );
+
+        TRAY_ICON.with(|tray| {
+            if let Some(tray_icon) = tray.lock().unwrap().as_ref() {
+                tray_icon.set_menu(Some(Box::new(menu)));
+            }
+        });
+    });
+}
+
 pub fn init_tray() {
-    // TODO: Implement tray menus
-    // Currently, the main thread will panic, stating that MudaMenuItem class already exists or something,
-    // probably because of tray_icon, dioxus, and dioxus-desktop using different version of muda.
-    // Could use dioxus trayicon instead of tray_icon crate, and patch dioxus-desktop to use the same version of muda
-    // as dioxus
+    // Menus only work because the default dioxus menu bar is disabled in main.rs,
+    // otherwise dioxus-desktop's muda registers the MudaMenuItem class first and the main thread panics
+    let menu = build_menu(&[], None, &[], None, None, false);
 
     let tray_icon = TrayIconBuilder::new()
-        .with_tooltip("AuraTimer: Time Remaining")
+        .with_tooltip("AuraTimer")
         .with_title("Focus: 25:00")
+        .with_menu(Box::new(menu))
+        .with_menu_on_left_click(false)
         .build()
         .unwrap();
 
@@ -103,6 +240,7 @@ pub fn init_tray() {
     });
 
     // redraw to make the system tray visible
+    #[cfg(target_os = "macos")]
     unsafe {
         let rl = CFRunLoopGetMain();
         if !rl.is_null() {
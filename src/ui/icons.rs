@@ -9,8 +9,14 @@ pub enum IconType {
     Restart,
     Skip,
     Revert,
+    Redo,
     CircleFilled,
     CircleOutlined,
+    Settings,
+    Keyboard,
+    Chart,
+    List,
+    Alarm,
 }
 
 #[derive(Props, Clone, PartialEq)]
@@ -73,6 +79,21 @@ pub fn Icon(props: Props) -> Element {
                 fill: "none",
             }
         ),
+        IconType::Redo => rsx!(
+            path {
+                d: "M0 0h24v24H0z",
+                fill: "none",
+                stroke: "none"
+            }
+            path {
+                d: "M15 14l4 -4l-4 -4",
+                fill: "none",
+            }
+            path {
+                d: "M19 10h-11a4 4 0 1 0 0 8h1",
+                fill: "none",
+            }
+        ),
         IconType::CircleFilled => rsx!(
             path {
                 d: "M0 0h24v24H0z",
@@ -94,6 +115,137 @@ pub fn Icon(props: Props) -> Element {
                 fill: "none",
             }
         ),
+        IconType::Settings => rsx!(
+            path {
+                d: "M0 0h24v24H0z",
+                fill: "none",
+                stroke: "none"
+            }
+            path {
+                d: "M10.325 4.317c.426 -1.756 2.924 -1.756 3.35 0a1.724 1.724 0 0 0 2.573 1.066c1.543 -.94 3.31 .826 2.37 2.37a1.724 1.724 0 0 0 1.065 2.572c1.756 .426 1.756 2.924 0 3.35a1.724 1.724 0 0 0 -1.066 2.573c.94 1.543 -.826 3.31 -2.37 2.37a1.724 1.724 0 0 0 -2.572 1.065c-.426 1.756 -2.924 1.756 -3.35 0a1.724 1.724 0 0 0 -2.573 -1.066c-1.543 .94 -3.31 -.826 -2.37 -2.37a1.724 1.724 0 0 0 -1.065 -2.572c-1.756 -.426 -1.756 -2.924 0 -3.35a1.724 1.724 0 0 0 1.066 -2.573c-.94 -1.543 .826 -3.31 2.37 -2.37c1 .608 2.296 .07 2.572 -1.065z",
+                fill: "none",
+            }
+            path {
+                d: "M9 12a3 3 0 1 0 6 0a3 3 0 0 0 -6 0",
+                fill: "none",
+            }
+        ),
+        IconType::Keyboard => rsx!(
+            path {
+                d: "M0 0h24v24H0z",
+                fill: "none",
+                stroke: "none"
+            }
+            path {
+                d: "M2 6m0 2a2 2 0 0 1 2 -2h16a2 2 0 0 1 2 2v8a2 2 0 0 1 -2 2h-16a2 2 0 0 1 -2 -2z",
+                fill: "none",
+            }
+            path {
+                d: "M6 10l0 .01",
+                fill: "none",
+            }
+            path {
+                d: "M10 10l0 .01",
+                fill: "none",
+            }
+            path {
+                d: "M14 10l0 .01",
+                fill: "none",
+            }
+            path {
+                d: "M18 10l0 .01",
+                fill: "none",
+            }
+            path {
+                d: "M6 14l0 .01",
+                fill: "none",
+            }
+            path {
+                d: "M18 14l0 .01",
+                fill: "none",
+            }
+            path {
+                d: "M10 14l4 .01",
+                fill: "none",
+            }
+        ),
+        IconType::Chart => rsx!(
+            path {
+                d: "M0 0h24v24H0z",
+                fill: "none",
+                stroke: "none"
+            }
+            path {
+                d: "M3 13a1 1 0 0 1 1 -1h4a1 1 0 0 1 1 1v6a1 1 0 0 1 -1 1h-4a1 1 0 0 1 -1 -1z",
+                fill: "none",
+            }
+            path {
+                d: "M15 9a1 1 0 0 1 1 -1h4a1 1 0 0 1 1 1v10a1 1 0 0 1 -1 1h-4a1 1 0 0 1 -1 -1z",
+                fill: "none",
+            }
+            path {
+                d: "M9 5a1 1 0 0 1 1 -1h4a1 1 0 0 1 1 1v14a1 1 0 0 1 -1 1h-4a1 1 0 0 1 -1 -1z",
+                fill: "none",
+            }
+            path {
+                d: "M4 20h14",
+                fill: "none",
+            }
+        ),
+        IconType::List => rsx!(
+            path {
+                d: "M0 0h24v24H0z",
+                fill: "none",
+                stroke: "none"
+            }
+            path {
+                d: "M3.5 5.5l1.5 1.5l2.5 -2.5",
+                fill: "none",
+            }
+            path {
+                d: "M3.5 11.5l1.5 1.5l2.5 -2.5",
+                fill: "none",
+            }
+            path {
+                d: "M3.5 17.5l1.5 1.5l2.5 -2.5",
+                fill: "none",
+            }
+            path {
+                d: "M11 6l9 0",
+                fill: "none",
+            }
+            path {
+                d: "M11 12l9 0",
+                fill: "none",
+            }
+            path {
+                d: "M11 18l9 0",
+                fill: "none",
+            }
+        ),
+        IconType::Alarm => rsx!(
+            path {
+                d: "M0 0h24v24H0z",
+                fill: "none",
+                stroke: "none"
+            }
+            path {
+                d: "M12 13m-7 0a7 7 0 1 0 14 0a7 7 0 1 0 -14 0",
+                fill: "none",
+            }
+            path {
+                d: "M12 10l0 3l2 0",
+                fill: "none",
+            }
+            path {
+                d: "M7 4l-2.75 2",
+                fill: "none",
+            }
+            path {
+                d: "M17 4l2.75 2",
+                fill: "none",
+            }
+        ),
     };
 
     rsx!(
@@ -0,0 +1,104 @@
+/// Actions that can be taken back and done again, each kept as whatever is needed to go back to the other side of it
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndoStack<T> {
+    // most recent last
+    undo: Vec<T>,
+    redo: Vec<T>,
+    /// Oldest actions are forgotten beyond this
+    limit: usize,
+}
+
+impl<T> UndoStack<T> {
+    pub const fn new(limit: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Remembers an action that is about to happen, a new action can't be redone after
+    pub fn push(&mut self, entry: T) {
+        self.undo.push(entry);
+        if self.undo.len() > self.limit {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// The action `undo` would take back
+    pub fn last(&self) -> Option<&T> {
+        self.undo.last()
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.undo.last_mut()
+    }
+
+    /// The action `redo` would do again
+    pub fn next(&self) -> Option<&T> {
+        self.redo.last()
+    }
+
+    /// Takes back the most recent action, `swap` goes back to before it and returns what is needed to redo it.
+    /// Returns whether there was anything to undo.
+    pub fn undo(&mut self, swap: impl FnOnce(T) -> T) -> bool {
+        let Some(entry) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(swap(entry));
+        true
+    }
+
+    /// Does the most recently undone action again, `swap` goes back to after it and returns what is needed to undo it
+    pub fn redo(&mut self, swap: impl FnOnce(T) -> T) -> bool {
+        let Some(entry) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(swap(entry));
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// What can be undone and what can be redone, most recent last
+    #[cfg(test)]
+    pub fn entries(&self) -> (&[T], &[T]) {
+        (&self.undo, &self.redo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forgets_the_oldest_actions_beyond_the_limit() {
+        let mut stack = UndoStack::new(2);
+        for entry in 1..=3 {
+            stack.push(entry);
+        }
+
+        let mut undone = Vec::new();
+        while stack.undo(|entry| {
+            undone.push(entry);
+            entry
+        }) {}
+        assert_eq!(undone, [3, 2]);
+    }
+
+    #[test]
+    fn a_new_action_drops_the_redo() {
+        let mut stack = UndoStack::new(5);
+        stack.push(1);
+        assert!(stack.undo(|entry| entry));
+        assert_eq!(stack.next(), Some(&1));
+
+        stack.push(2);
+        assert_eq!(stack.next(), None);
+        assert!(!stack.redo(|entry| entry));
+    }
+}
@@ -0,0 +1,456 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Local, TimeDelta};
+use dioxus::prelude::*;
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::broadcast::error::TryRecvError;
+use tracing::{error, info};
+
+use crate::{
+    bus::{subscribe_events, SessionEvent},
+    settings::SETTINGS,
+    storage::write_json,
+};
+
+/// Deliveries are dropped after this many failed attempts
+const MAX_ATTEMPTS: u32 = 8;
+const FIRST_RETRY_SECS: i64 = 5;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Delivery {
+    url: String,
+    payload: serde_json::Value,
+    attempts: u32,
+    next_attempt_at: DateTime<Local>,
+}
+
+fn outbox_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("AuraTimer").join("webhook_outbox.json"))
+}
+
+/// Webhook deliveries that haven't succeeded yet, kept in a file so they survive a restart
+pub struct Outbox {
+    path: PathBuf,
+    deliveries: Vec<Delivery>,
+}
+
+impl Outbox {
+    /// Loads the deliveries kept at `path`, none if the file doesn't exist yet
+    pub fn load(path: PathBuf) -> Self {
+        let deliveries = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                error!("Failed to parse webhook outbox: {}", e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { path, deliveries }
+    }
+
+    fn save(&self) {
+        if let Err(e) = self.write() {
+            error!("Failed to save webhook outbox: {}", e);
+        }
+    }
+
+    fn write(&self) -> Result<(), Box<dyn std::error::Error>> {
+        write_json(&self.path, &self.deliveries)
+    }
+
+    /// Queues `payload` for each of `urls`, due right away
+    pub fn queue(&mut self, urls: Vec<String>, payload: serde_json::Value) {
+        let now = Local::now();
+        self.deliveries.extend(urls.into_iter().map(|url| Delivery {
+            url,
+            payload: payload.clone(),
+            attempts: 0,
+            next_attempt_at: now,
+        }));
+        self.save();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deliveries.is_empty()
+    }
+
+    /// Sends the deliveries that are due, all webhooks at once so a slow one doesn't hold up the others.
+    /// Each webhook gets its deliveries oldest first and stops at the first failure, so receivers see events in order.
+    pub async fn deliver_due(&mut self, client: &reqwest::Client) {
+        let now = Local::now();
+        let mut urls: Vec<&str> = Vec::new();
+        for delivery in &self.deliveries {
+            if !urls.contains(&delivery.url.as_str()) {
+                urls.push(&delivery.url);
+            }
+        }
+
+        let sends = urls.into_iter().map(|url| {
+            let deliveries = &self.deliveries;
+            async move {
+                // index and error of every delivery that was attempted
+                let mut results = Vec::new();
+                for (index, delivery) in deliveries.iter().enumerate() {
+                    if delivery.url != url {
+                        continue;
+                    }
+                    if delivery.next_attempt_at > now {
+                        break;
+                    }
+                    let result = send(client, delivery).await;
+                    let failed = result.is_err();
+                    results.push((index, result.err()));
+                    if failed {
+                        break;
+                    }
+                }
+                results
+            }
+        });
+        let results: Vec<(usize, Option<reqwest::Error>)> =
+            join_all(sends).await.into_iter().flatten().collect();
+        if results.is_empty() {
+            return;
+        }
+
+        let mut delivered = Vec::new();
+        for (index, error) in results {
+            let delivery = &mut self.deliveries[index];
+            let event = delivery.payload["event"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            match error {
+                None => delivered.push(index),
+                Some(e) if delivery.attempts + 1 >= MAX_ATTEMPTS => {
+                    error!(
+                        "Giving up on {} webhook to {} after {} attempts: {}",
+                        event, delivery.url, MAX_ATTEMPTS, e
+                    );
+                    delivered.push(index);
+                }
+                Some(e) => {
+                    delivery.attempts += 1;
+                    // 5s, 10s, 20s, … between attempts
+                    let delay = TimeDelta::seconds(FIRST_RETRY_SECS << (delivery.attempts - 1));
+                    delivery.next_attempt_at = Local::now() + delay;
+                    error!(
+                        "Failed to deliver {} webhook to {}, retrying in {}s: {}",
+                        event,
+                        delivery.url,
+                        delay.num_seconds(),
+                        e
+                    );
+                }
+            }
+        }
+
+        delivered.sort_unstable();
+        for index in delivered.into_iter().rev() {
+            self.deliveries.remove(index);
+        }
+        self.save();
+    }
+}
+
+async fn send(client: &reqwest::Client, delivery: &Delivery) -> Result<(), reqwest::Error> {
+    let response = client
+        .post(&delivery.url)
+        .json(&delivery.payload)
+        .send()
+        .await?
+        .error_for_status()?;
+    info!(
+        "Delivered {} webhook to {}: {}",
+        delivery.payload["event"].as_str().unwrap_or_default(),
+        delivery.url,
+        response.status()
+    );
+    Ok(())
+}
+
+fn payload(event: &SessionEvent) -> serde_json::Value {
+    json!({
+        "event": event.kind.name(),
+        "at": event.at.to_rfc3339(),
+        "phase": event.phase.name(),
+        "phase_name": event.phase_name,
+        "planned_seconds": event.planned_secs,
+        "remaining_seconds": event.remaining_secs,
+        "session_number": event.session_number,
+        "task": event.task,
+    })
+}
+
+/// Queues a delivery to every configured webhook for each session event and works through the outbox
+pub fn use_webhooks() {
+    use_future(|| async {
+        let Some(path) = outbox_path() else {
+            error!("No data directory available, webhooks won't be sent");
+            return;
+        };
+        let mut outbox = Outbox::load(path);
+        let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Failed to create webhook client: {}", e);
+                return;
+            }
+        };
+        let mut events = subscribe_events();
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+        loop {
+            interval.tick().await;
+
+            // events are kept by the channel while deliveries are on their way
+            loop {
+                match events.try_recv() {
+                    Ok(event) => {
+                        let urls = SETTINGS.peek().webhook_urls();
+                        if !urls.is_empty() {
+                            outbox.queue(urls, payload(&event));
+                        }
+                    }
+                    Err(TryRecvError::Lagged(skipped)) => {
+                        error!("Skipped {} session events for webhooks", skipped);
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Closed) => return,
+                }
+            }
+
+            if !outbox.is_empty() {
+                outbox.deliver_due(&client).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use super::*;
+
+    type Received = Arc<Mutex<Vec<String>>>;
+
+    /// Reads a request and returns its body
+    async fn read_body(stream: &mut TcpStream) -> Option<String> {
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+        loop {
+            let read = stream.read(&mut buffer).await.ok()?;
+            if read == 0 {
+                return None;
+            }
+            request.extend_from_slice(&buffer[..read]);
+
+            let text = String::from_utf8_lossy(&request);
+            let Some(end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let length = text[..end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse().ok())?
+                })
+                .unwrap_or(0);
+            if request.len() >= end + 4 + length {
+                return Some(text[end + 4..].to_string());
+            }
+        }
+    }
+
+    /// A webhook on a local port that answers with `statuses` in turn and keeps answering with the last one,
+    /// returns its url and the events it received
+    async fn webhook(statuses: &'static [u16]) -> (String, Received) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received = Received::default();
+
+        let events = received.clone();
+        tokio::spawn(async move {
+            let mut statuses = statuses.iter().peekable();
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let Some(body) = read_body(&mut stream).await else {
+                    continue;
+                };
+                let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+                events
+                    .lock()
+                    .unwrap()
+                    .push(payload["event"].as_str().unwrap().to_string());
+
+                let status = if statuses.len() > 1 {
+                    statuses.next().unwrap()
+                } else {
+                    statuses.peek().unwrap()
+                };
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (url, received)
+    }
+
+    /// A webhook that accepts connections and never answers
+    async fn hung_webhook() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut open = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                open.push(stream);
+            }
+        });
+        url
+    }
+
+    fn client(timeout: Duration) -> reqwest::Client {
+        reqwest::Client::builder()
+            .timeout(timeout)
+            .no_proxy()
+            .build()
+            .unwrap()
+    }
+
+    fn event(name: &str) -> serde_json::Value {
+        json!({ "event": name })
+    }
+
+    fn received(events: &Received) -> Vec<String> {
+        events.lock().unwrap().clone()
+    }
+
+    /// Makes every delivery due as if the backoff had passed
+    fn wait_out_backoff(outbox: &mut Outbox) {
+        for delivery in &mut outbox.deliveries {
+            delivery.next_attempt_at = Local::now();
+        }
+    }
+
+    fn assert_retry_in(delivery: &Delivery, secs: i64) {
+        let delay = delivery.next_attempt_at - Local::now();
+        assert!(
+            delay > TimeDelta::seconds(secs - 1) && delay <= TimeDelta::seconds(secs),
+            "retry in {:?} instead of {}s",
+            delay,
+            secs
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_with_backoff_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let (url, events) = webhook(&[500, 503, 200]).await;
+        let client = client(REQUEST_TIMEOUT);
+        let mut outbox = Outbox::load(dir.path().join("outbox.json"));
+
+        outbox.queue(vec![url.clone()], event("focus_start"));
+        outbox.queue(vec![url], event("pause"));
+
+        // the second event waits for the first one
+        outbox.deliver_due(&client).await;
+        assert_eq!(received(&events), ["focus_start"]);
+        assert_eq!(outbox.deliveries.len(), 2);
+        assert_eq!(outbox.deliveries[0].attempts, 1);
+        assert_retry_in(&outbox.deliveries[0], FIRST_RETRY_SECS);
+
+        // nothing is sent before the retry is due
+        outbox.deliver_due(&client).await;
+        assert_eq!(received(&events).len(), 1);
+
+        wait_out_backoff(&mut outbox);
+        outbox.deliver_due(&client).await;
+        assert_eq!(outbox.deliveries[0].attempts, 2);
+        assert_retry_in(&outbox.deliveries[0], FIRST_RETRY_SECS * 2);
+
+        wait_out_backoff(&mut outbox);
+        outbox.deliver_due(&client).await;
+        assert_eq!(
+            received(&events),
+            ["focus_start", "focus_start", "focus_start", "pause"]
+        );
+        assert!(outbox.is_empty());
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_the_last_attempt() {
+        let dir = tempfile::tempdir().unwrap();
+        let (url, events) = webhook(&[500]).await;
+        let mut outbox = Outbox::load(dir.path().join("outbox.json"));
+
+        outbox.queue(vec![url], event("focus_end"));
+        outbox.deliveries[0].attempts = MAX_ATTEMPTS - 1;
+        outbox.deliver_due(&client(REQUEST_TIMEOUT)).await;
+
+        assert_eq!(received(&events), ["focus_end"]);
+        assert!(outbox.is_empty());
+    }
+
+    #[tokio::test]
+    async fn keeps_failed_deliveries_across_a_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+        let (url, events) = webhook(&[500, 200]).await;
+        let client = client(REQUEST_TIMEOUT);
+
+        let mut outbox = Outbox::load(path.clone());
+        outbox.queue(vec![url.clone()], event("break_start"));
+        outbox.deliver_due(&client).await;
+
+        let mut outbox = Outbox::load(path.clone());
+        assert_eq!(outbox.deliveries.len(), 1);
+        assert_eq!(outbox.deliveries[0].url, url);
+        assert_eq!(outbox.deliveries[0].payload, event("break_start"));
+        assert_eq!(outbox.deliveries[0].attempts, 1);
+        assert_retry_in(&outbox.deliveries[0], FIRST_RETRY_SECS);
+
+        wait_out_backoff(&mut outbox);
+        outbox.deliver_due(&client).await;
+        assert_eq!(received(&events), ["break_start", "break_start"]);
+        assert!(Outbox::load(path).is_empty());
+    }
+
+    #[tokio::test]
+    async fn hung_webhooks_dont_hold_up_the_others() {
+        let dir = tempfile::tempdir().unwrap();
+        let timeout = Duration::from_millis(500);
+        let (url, events) = webhook(&[200]).await;
+        let hung = [hung_webhook().await, hung_webhook().await];
+        let mut outbox = Outbox::load(dir.path().join("outbox.json"));
+
+        for name in ["focus_start", "pause", "resume"] {
+            outbox.queue(
+                vec![hung[0].clone(), url.clone(), hung[1].clone()],
+                event(name),
+            );
+        }
+        let started = Instant::now();
+        outbox.deliver_due(&client(timeout)).await;
+
+        // both hung webhooks timed out at the same time
+        assert!(started.elapsed() < timeout * 2);
+        assert_eq!(received(&events), ["focus_start", "pause", "resume"]);
+        assert_eq!(outbox.deliveries.len(), 6);
+        assert!(outbox.deliveries.iter().all(|d| hung.contains(&d.url)));
+        assert_eq!(outbox.deliveries.iter().map(|d| d.attempts).sum::<u32>(), 2);
+    }
+}
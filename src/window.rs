@@ -1,17 +1,98 @@
 use dioxus::{desktop::window, html::input_data::MouseButton, prelude::*};
-use objc2::{msg_send, runtime::AnyObject};
 
-pub unsafe fn set_transparent_titlebar(ns_window: *mut AnyObject) {
-    let _: () = msg_send![ns_window, setTitlebarAppearsTransparent: true];
+#[cfg(target_os = "macos")]
+pub use macos::{init_app_menu, set_transparent_titlebar};
 
-    let mut style_mask: u64 = msg_send![ns_window, styleMask];
+// the app menu and the title bar style only exist on macOS, other platforms keep the native decorations
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::sync::Mutex;
 
-    const NS_FULL_SIZE_CONTENT_VIEW_WINDOW_MASK: u64 = 1 << 15;
-    style_mask |= NS_FULL_SIZE_CONTENT_VIEW_WINDOW_MASK;
-    let _: () = msg_send![ns_window, setStyleMask: style_mask];
+    use dioxus::desktop::{tao::platform::macos::WindowExtMacOS, window};
+    use objc2::{msg_send, runtime::AnyObject};
+    use tracing::error;
+    use tray_icon::menu::{Menu, PredefinedMenuItem, Submenu};
 
-    let _: () = msg_send![ns_window, setTitleVisibility: 1i64];
-    let _: () = msg_send![ns_window, setTitlebarAppearsTransparent: true];
+    thread_local! {
+        static APP_MENU: Mutex<Option<Menu>> = const { Mutex::new(None) };
+    }
+
+    // replaces the default dioxus menu bar, which can't be used together with the tray menu
+    pub fn init_app_menu() {
+        let app_menu = Submenu::with_items(
+            "AuraTimer",
+            true,
+            &[
+                &PredefinedMenuItem::about(None, None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::hide(None),
+                &PredefinedMenuItem::hide_others(None),
+                &PredefinedMenuItem::show_all(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::quit(None),
+            ],
+        )
+        .unwrap();
+
+        // needed for copy and paste in text fields
+        let edit_menu = Submenu::with_items(
+            "Edit",
+            true,
+            &[
+                &PredefinedMenuItem::undo(None),
+                &PredefinedMenuItem::redo(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::cut(None),
+                &PredefinedMenuItem::copy(None),
+                &PredefinedMenuItem::paste(None),
+                &PredefinedMenuItem::select_all(None),
+            ],
+        )
+        .unwrap();
+
+        let window_menu = Submenu::with_items(
+            "Window",
+            true,
+            &[
+                &PredefinedMenuItem::minimize(None),
+                &PredefinedMenuItem::close_window(None),
+            ],
+        )
+        .unwrap();
+
+        let menu = Menu::with_items(&[&app_menu, &edit_menu, &window_menu]).unwrap();
+        menu.init_for_nsapp();
+
+        APP_MENU.with(|m| {
+            *m.lock().unwrap() = Some(menu);
+        });
+    }
+
+    /// Lets the window content reach under the title bar, which is dragged through `WindowDragArea` instead
+    pub fn set_transparent_titlebar() {
+        let ns_view: *mut AnyObject = window().ns_view().cast();
+        unsafe {
+            let ns_window: *mut AnyObject = msg_send![ns_view, window];
+            if ns_window.is_null() {
+                error!("ns_window is null, unable to set transparent titlebar");
+                return;
+            }
+            set_titlebar_style(ns_window);
+        }
+    }
+
+    unsafe fn set_titlebar_style(ns_window: *mut AnyObject) {
+        let _: () = msg_send![ns_window, setTitlebarAppearsTransparent: true];
+
+        let mut style_mask: u64 = msg_send![ns_window, styleMask];
+
+        const NS_FULL_SIZE_CONTENT_VIEW_WINDOW_MASK: u64 = 1 << 15;
+        style_mask |= NS_FULL_SIZE_CONTENT_VIEW_WINDOW_MASK;
+        let _: () = msg_send![ns_window, setStyleMask: style_mask];
+
+        let _: () = msg_send![ns_window, setTitleVisibility: 1i64];
+        let _: () = msg_send![ns_window, setTitlebarAppearsTransparent: true];
+    }
 }
 
 // adapted from Freya's WindowDragArea